@@ -0,0 +1,471 @@
+use std::io;
+use std::path::Path;
+use tokio::fs;
+use crate::stock::{parse_stock, Stock, STOCK_FILE};
+pub use pizzeria_shared::delivery::{parse_delivery_settings, DeliverySettings};
+pub use pizzeria_shared::food::FoodInfo;
+
+pub const TOPPINGS_FILE: &str = "toppings_text";
+pub const PREBUILDS_FILE: &str = "pizza_prebuilds_text";
+pub const BUNDLES_FILE: &str = "bundles_text";
+pub const ITEMS_FILE: &str = "catalog_items_text";
+pub const LIMITS_FILE: &str = "topping_limits_text";
+pub const DELIVERY_FILE: &str = "delivery_text";
+
+pub const DEFAULT_MAX_PER_TOPPING: u32 = 5;
+pub const DEFAULT_MAX_TOTAL_TOPPINGS: u32 = 12;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Category {
+    Pizza,
+    Drink,
+    Dessert,
+    Side,
+}
+
+impl Category {
+    pub fn parse(text: &str) -> Option<Category> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "pizza" => Some(Category::Pizza),
+            "drink" => Some(Category::Drink),
+            "dessert" => Some(Category::Dessert),
+            "side" => Some(Category::Side),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Topping {
+    pub name: String,
+    pub price: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Prebuilt {
+    pub name: String,
+    pub toppings: Vec<String>,
+    pub base_price: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Item {
+    pub name: String,
+    pub category: Category,
+    pub price: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BundleSlot {
+    pub label: String,
+    pub category: Category,     //bei leerer Liste: jeder Artikel dieser Kategorie
+    pub allowed: Vec<String>,
+}
+
+impl BundleSlot {
+    pub fn allows(&self, catalog: &Catalog, item: &str) -> bool {
+        if self.allowed.is_empty() {
+            catalog.category_of(item) == Some(self.category)
+        } else {
+            self.allowed.iter().any(|allowed| allowed == item)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Bundle {
+    pub name: String,
+    pub slots: Vec<BundleSlot>,
+    pub price: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ToppingLimit {
+    pub name: String,
+    pub min: u32,
+    pub max: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ToppingLimits {
+    pub min_total: u32,
+    pub max_total: u32,
+    pub per_topping: Vec<ToppingLimit>,
+}
+
+impl Default for ToppingLimits {
+    fn default() -> Self {
+        Self {
+            min_total: 0,
+            max_total: DEFAULT_MAX_TOTAL_TOPPINGS,
+            per_topping: Vec::new(),
+        }
+    }
+}
+
+impl ToppingLimits {
+    fn limit(&self, name: &str) -> Option<&ToppingLimit> {
+        self.per_topping.iter().find(|limit| limit.name == name)
+    }
+
+    pub fn min_for(&self, name: &str) -> u32 {
+        self.limit(name).map(|limit| limit.min).unwrap_or(0)
+    }
+
+    pub fn max_for(&self, name: &str) -> u32 {
+        self.limit(name).map(|limit| limit.max).unwrap_or(DEFAULT_MAX_PER_TOPPING)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Catalog {
+    pub toppings: Vec<Topping>,
+    pub prebuilts: Vec<Prebuilt>,
+    pub items: Vec<Item>,
+    pub bundles: Vec<Bundle>,
+    pub limits: ToppingLimits,
+    pub delivery: DeliverySettings,
+    pub stock: Stock,
+}
+
+impl Catalog {
+    pub fn topping(&self, name: &str) -> Option<&Topping> {
+        self.toppings.iter().find(|topping| topping.name == name)
+    }
+
+    pub fn prebuilt(&self, name: &str) -> Option<&Prebuilt> {
+        self.prebuilts.iter().find(|prebuilt| prebuilt.name == name)
+    }
+
+    pub fn item(&self, name: &str) -> Option<&Item> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    pub fn category_of(&self, name: &str) -> Option<Category> {
+        if self.prebuilt(name).is_some() {
+            Some(Category::Pizza)
+        } else {
+            self.item(name).map(|item| item.category)
+        }
+    }
+
+    pub fn bundle(&self, name: &str) -> Option<&Bundle> {
+        self.bundles.iter().find(|bundle| bundle.name == name)
+    }
+}
+
+// Format: <Name>#<Preis>[#<Kennzeichnungen>], Kennzeichnungen wie "vegetarian|lactose" (siehe FoodInfo::parse)
+pub fn parse_toppings(content: &str) -> Result<Vec<Topping>, String> {
+    let mut toppings = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut split = line.split('#');
+        let name = split.next().unwrap_or("").trim();
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Topping-Name fehlt", lineno + 1));
+        }
+        let price_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Topping-Preis fehlt", lineno + 1))?;
+        let price = price_text.trim().parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Preis '{}'", lineno + 1, price_text))?;
+        FoodInfo::parse(split.next().unwrap_or(""))
+            .map_err(|e| format!("Zeile {}: {}", lineno + 1, e))?;
+
+        toppings.push(Topping { name: name.to_string(), price });
+    }
+
+    Ok(toppings)
+}
+
+// Format: <Pizza-Name>#<Topping1|Topping2|…>#<Basispreis>
+pub fn parse_prebuilts(content: &str) -> Result<Vec<Prebuilt>, String> {
+    let mut prebuilts = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut split = line.splitn(3, '#');
+        let name = split.next().unwrap_or("").trim();
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Pizza-Name fehlt", lineno + 1));
+        }
+        let topping_names = split.next().unwrap_or("-");
+        let base_price_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Basispreis fehlt", lineno + 1))?;
+        let base_price = base_price_text.trim().parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Basispreis '{}'", lineno + 1, base_price_text))?;
+
+        let toppings = topping_names
+            .split('|')
+            .map(|topping| topping.trim())
+            .filter(|topping| !topping.is_empty() && *topping != "-")
+            .map(String::from)
+            .collect();
+
+        prebuilts.push(Prebuilt { name: name.to_string(), toppings, base_price });
+    }
+
+    Ok(prebuilts)
+}
+
+// Format: <Kategorie>#<Name>#<Preis>, Kategorie: drink, dessert oder side
+// Pizzen werden weiterhin in pizza_prebuilds_text gepflegt.
+pub fn parse_items(content: &str) -> Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut split = line.splitn(3, '#');
+        let category_text = split.next().unwrap_or("");
+        let category = match Category::parse(category_text) {
+            Some(Category::Pizza) =>
+                return Err(format!("Zeile {}: Pizzen gehören in {PREBUILDS_FILE}", lineno + 1)),
+            Some(category) => category,
+            None => return Err(format!("Zeile {}: Unbekannte Kategorie '{}'", lineno + 1, category_text)),
+        };
+        let name = split.next().unwrap_or("").trim();
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Artikel-Name fehlt", lineno + 1));
+        }
+        let price_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Artikel-Preis fehlt", lineno + 1))?;
+        let price = price_text.trim().parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Preis '{}'", lineno + 1, price_text))?;
+
+        items.push(Item { name: name.to_string(), category, price });
+    }
+
+    Ok(items)
+}
+
+// Format: <Bundle-Name>#<Slot1>;<Slot2>;…#<Preis>
+// Slot: <Bezeichnung>:<Artikel1|Artikel2|…>, <Bezeichnung>:* für jede Prebuilt-Pizza
+// oder <Bezeichnung>:*<Kategorie> für jeden Artikel der Kategorie
+pub fn parse_bundles(content: &str) -> Result<Vec<Bundle>, String> {
+    let mut bundles = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut split = line.splitn(3, '#');
+        let name = split.next().unwrap_or("").trim();
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Bundle-Name fehlt", lineno + 1));
+        }
+        let slots_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Bundle-Slots fehlen", lineno + 1))?;
+        let price_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Bundle-Preis fehlt", lineno + 1))?;
+        let price = price_text.trim().parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Preis '{}'", lineno + 1, price_text))?;
+
+        let mut slots = Vec::new();
+        for slot in slots_text.split(';').map(str::trim).filter(|slot| !slot.is_empty()) {
+            let (label, allowed) = slot.split_once(':')
+                .ok_or_else(|| format!("Zeile {}: Ungültiger Slot '{}'", lineno + 1, slot))?;
+            let allowed = allowed.trim();
+            let (category, allowed) = match allowed.strip_prefix('*') {
+                Some("") => (Category::Pizza, Vec::new()),
+                Some(category_text) => {
+                    let category = Category::parse(category_text)
+                        .ok_or_else(|| format!("Zeile {}: Unbekannte Kategorie '{}'", lineno + 1, category_text))?;
+                    (category, Vec::new())
+                }
+                None => {
+                    let allowed = allowed
+                        .split('|')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(String::from)
+                        .collect();
+                    (Category::Pizza, allowed)
+                }
+            };
+            slots.push(BundleSlot { label: label.trim().to_string(), category, allowed });
+        }
+        if slots.is_empty() {
+            return Err(format!("Zeile {}: Bundle ohne Slots", lineno + 1));
+        }
+
+        bundles.push(Bundle { name: name.to_string(), slots, price });
+    }
+
+    Ok(bundles)
+}
+
+// Format: <Topping>#<Min>#<Max> je Topping oder *#<Min>#<Max> für die Gesamtzahl pro Pizza
+pub fn parse_topping_limits(content: &str) -> Result<ToppingLimits, String> {
+    let mut limits = ToppingLimits::default();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('#').map(str::trim).collect();
+        let [name, min_text, max_text] = fields[..] else {
+            return Err(format!("Zeile {}: erwartet 'Topping#Min#Max'", lineno + 1));
+        };
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Topping-Name fehlt", lineno + 1));
+        }
+        let min = min_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiges Minimum '{}'", lineno + 1, min_text))?;
+        let max = max_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiges Maximum '{}'", lineno + 1, max_text))?;
+        if min > max {
+            return Err(format!("Zeile {}: Minimum {} größer als Maximum {}", lineno + 1, min, max));
+        }
+
+        if name == "*" {
+            limits.min_total = min;
+            limits.max_total = max;
+        } else {
+            limits.per_topping.push(ToppingLimit { name: name.to_string(), min, max });
+        }
+    }
+
+    Ok(limits)
+}
+
+//fehlende Datei = leerer Inhalt
+pub async fn read_optional(path: impl AsRef<Path>) -> io::Result<String> {
+    match fs::read_to_string(path).await {
+        Ok(content) => Ok(content),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(error) => Err(error),
+    }
+}
+
+pub async fn load_catalog() -> Result<Catalog, String> {
+    let toppings = read_optional(TOPPINGS_FILE).await
+        .map_err(|error| format!("{TOPPINGS_FILE}: {error}"))?;
+    let prebuilts = read_optional(PREBUILDS_FILE).await
+        .map_err(|error| format!("{PREBUILDS_FILE}: {error}"))?;
+    let items = read_optional(ITEMS_FILE).await
+        .map_err(|error| format!("{ITEMS_FILE}: {error}"))?;
+    let bundles = read_optional(BUNDLES_FILE).await
+        .map_err(|error| format!("{BUNDLES_FILE}: {error}"))?;
+    let limits = read_optional(LIMITS_FILE).await
+        .map_err(|error| format!("{LIMITS_FILE}: {error}"))?;
+    let delivery = read_optional(DELIVERY_FILE).await
+        .map_err(|error| format!("{DELIVERY_FILE}: {error}"))?;
+    let stock = read_optional(STOCK_FILE).await
+        .map_err(|error| format!("{STOCK_FILE}: {error}"))?;
+
+    Ok(Catalog {
+        toppings: parse_toppings(&toppings).map_err(|error| format!("{TOPPINGS_FILE}: {error}"))?,
+        prebuilts: parse_prebuilts(&prebuilts).map_err(|error| format!("{PREBUILDS_FILE}: {error}"))?,
+        items: parse_items(&items).map_err(|error| format!("{ITEMS_FILE}: {error}"))?,
+        bundles: parse_bundles(&bundles).map_err(|error| format!("{BUNDLES_FILE}: {error}"))?,
+        limits: parse_topping_limits(&limits).map_err(|error| format!("{LIMITS_FILE}: {error}"))?,
+        delivery: parse_delivery_settings(&delivery).map_err(|error| format!("{DELIVERY_FILE}: {error}"))?,
+        stock: parse_stock(&stock).map_err(|error| format!("{STOCK_FILE}: {error}"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toppings_with_food_tags() {
+        let toppings = parse_toppings("Corn#4#vegan\nXtra Cheese#8#vegetarian|lactose").unwrap();
+        assert_eq!(toppings[1], Topping { name: String::from("Xtra Cheese"), price: 8 });
+
+        assert!(parse_toppings("Mozzarella#3#vegetarian|Lactose").is_ok());
+        assert_eq!(parse_toppings("Corn#4\nChili#2#spicy"), Err(String::from("Zeile 2: Unbekannte Kennzeichnung 'spicy'")));
+    }
+
+    #[test]
+    fn should_parse_prebuilts_without_toppings() {
+        let prebuilts = parse_prebuilts("Margherita#-#4\nHawaii#A-Pineapple|Ham#4\n").unwrap();
+
+        assert_eq!(prebuilts, vec![
+            Prebuilt { name: String::from("Margherita"), toppings: vec![], base_price: 4 },
+            Prebuilt {
+                name: String::from("Hawaii"),
+                toppings: vec![String::from("A-Pineapple"), String::from("Ham")],
+                base_price: 4,
+            },
+        ]);
+    }
+
+    #[test]
+    fn should_parse_bundle_slots() {
+        let bundles = parse_bundles("Family Deal#Pizza:*;Pizza:Margherita|Funghi;Drink:*drink#14").unwrap();
+
+        assert_eq!(bundles, vec![Bundle {
+            name: String::from("Family Deal"),
+            slots: vec![
+                BundleSlot { label: String::from("Pizza"), category: Category::Pizza, allowed: vec![] },
+                BundleSlot {
+                    label: String::from("Pizza"),
+                    category: Category::Pizza,
+                    allowed: vec![String::from("Margherita"), String::from("Funghi")],
+                },
+                BundleSlot { label: String::from("Drink"), category: Category::Drink, allowed: vec![] },
+            ],
+            price: 14,
+        }]);
+    }
+
+    #[test]
+    fn should_check_bundle_slot_against_category() {
+        let catalog = Catalog {
+            prebuilts: parse_prebuilts("Hawaii#-#4").unwrap(),
+            items: parse_items("drink#Cola#3\ndessert#Tiramisu#5").unwrap(),
+            bundles: parse_bundles("Deal#Pizza:*;Drink:*drink#9").unwrap(),
+            ..Catalog::default()
+        };
+        let slots = &catalog.bundles[0].slots;
+
+        assert!(slots[0].allows(&catalog, "Hawaii"));
+        assert!(!slots[0].allows(&catalog, "Cola"));
+        assert!(slots[1].allows(&catalog, "Cola"));
+        assert!(!slots[1].allows(&catalog, "Tiramisu"));
+    }
+
+    #[test]
+    fn should_reject_pizza_in_items_file() {
+        assert!(parse_items("pizza#Calzone#9").is_err());
+        assert!(parse_items("soup#Minestrone#5").is_err());
+    }
+
+    #[test]
+    fn should_parse_topping_limits() {
+        let limits = parse_topping_limits("*#1#6\nXtra Cheese#0#3").unwrap();
+
+        assert_eq!(limits.min_total, 1);
+        assert_eq!(limits.max_total, 6);
+        assert_eq!(limits.max_for("Xtra Cheese"), 3);
+        assert_eq!(limits.max_for("Ham"), DEFAULT_MAX_PER_TOPPING);
+        assert!(parse_topping_limits("Ham#3#1").is_err());
+    }
+
+    #[test]
+    fn should_reject_topping_without_price() {
+        assert!(parse_toppings("Ham").is_err());
+    }
+}
@@ -35,3 +35,132 @@ impl Error for ConfigError {
         }
     }
 }
+
+#[derive(Debug)]
+pub enum PricingError {
+    InvalidLine {
+        line: String,
+    },
+    UnknownPizza {
+        name: String,
+    },
+    UnknownTopping {
+        name: String,
+    },
+    UnknownItem {
+        name: String,
+    },
+    UnknownBundle {
+        name: String,
+    },
+    BundleSlotCount {
+        name: String,
+        expected: usize,
+        actual: usize,
+    },
+    NotAllowedInSlot {
+        bundle: String,
+        slot: String,
+        item: String,
+    },
+    ToppingAboveMaximum {
+        name: String,
+        max: u32,
+    },
+    ToppingBelowMinimum {
+        name: String,
+        min: u32,
+    },
+    TooManyToppings {
+        max: u32,
+    },
+    TooFewToppings {
+        min: u32,
+    },
+    BelowDeliveryMinimum {
+        minimum: u32,
+    },
+    OutOfStock {
+        name: String,
+        available: u32,
+    },
+    UsageTooLarge {
+        name: String,
+    },
+}
+
+impl Display for PricingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PricingError::InvalidLine { line } =>
+                write!(f, "Ungültige Bestellzeile '{line}'"),
+            PricingError::UnknownPizza { name } =>
+                write!(f, "Unbekannte Pizza '{name}'"),
+            PricingError::UnknownTopping { name } =>
+                write!(f, "Unbekanntes Topping '{name}'"),
+            PricingError::UnknownItem { name } =>
+                write!(f, "Unbekannter Artikel '{name}'"),
+            PricingError::UnknownBundle { name } =>
+                write!(f, "Unbekanntes Bundle '{name}'"),
+            PricingError::BundleSlotCount { name, expected, actual } =>
+                write!(f, "Bundle '{name}' erwartet {expected} Auswahlen, erhalten {actual}"),
+            PricingError::NotAllowedInSlot { bundle, slot, item } =>
+                write!(f, "'{item}' ist im Bundle '{bundle}' nicht als '{slot}' erlaubt"),
+            PricingError::ToppingAboveMaximum { name, max } =>
+                write!(f, "Maximal {max}x {name} pro Pizza"),
+            PricingError::ToppingBelowMinimum { name, min } =>
+                write!(f, "Mindestens {min}x {name} pro Pizza erforderlich"),
+            PricingError::TooManyToppings { max } =>
+                write!(f, "Maximal {max} Toppings pro Pizza"),
+            PricingError::TooFewToppings { min } =>
+                write!(f, "Mindestens {min} Toppings pro Pizza erforderlich"),
+            PricingError::BelowDeliveryMinimum { minimum } =>
+                write!(f, "Mindestbestellwert für Lieferung: {minimum}.00$"),
+            PricingError::OutOfStock { name, available: 0 } =>
+                write!(f, "{name} ist ausverkauft"),
+            PricingError::OutOfStock { name, available } =>
+                write!(f, "Nur noch {available}x {name} vorrätig"),
+            PricingError::UsageTooLarge { name } =>
+                write!(f, "Verbrauch von '{name}' zu groß"),
+        }
+    }
+}
+
+impl Error for PricingError {}
+
+#[derive(Debug, PartialEq)]
+pub enum RefundError {
+    UnknownOrder {
+        number: usize,
+    },
+    RefundOfRefund {
+        number: usize,
+    },
+    AlreadyRefunded {
+        number: usize,
+        refund: usize,
+    },
+    MissingReason,
+    InvalidPrice {
+        number: usize,
+    },
+}
+
+impl Display for RefundError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RefundError::UnknownOrder { number } =>
+                write!(f, "Unbekannte Bestellung #{number}"),
+            RefundError::RefundOfRefund { number } =>
+                write!(f, "#{number} ist selbst ein Storno"),
+            RefundError::AlreadyRefunded { number, refund } =>
+                write!(f, "Bestellung #{number} wurde bereits mit #{refund} storniert"),
+            RefundError::MissingReason =>
+                write!(f, "Für ein Storno ist ein Grund erforderlich"),
+            RefundError::InvalidPrice { number } =>
+                write!(f, "Bestellung #{number} hat keinen gültigen Preis"),
+        }
+    }
+}
+
+impl Error for RefundError {}
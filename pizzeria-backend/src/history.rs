@@ -0,0 +1,300 @@
+//Bestellhistorie aus transactions.log: Bestellnummer = Nummer der (nicht-leeren) Zeile
+use crate::custom_error::RefundError;
+use pizzeria_shared::transaction_log::{escape_transaction_field, split_transaction_fields};
+
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+pub const MAX_PAGE_SIZE: usize = 100;
+//Bestellart-Feld einer Storno-Buchung, danach folgt die Nummer der stornierten Bestellung
+pub const REFUND_TYPE: &str = "refund";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LoggedTransaction {
+    pub number: usize,
+    pub line: String,           //Zeile unverändert (maskiert) für die Antwort
+    pub fields: Vec<String>,    //entmaskierte Felder
+}
+
+impl LoggedTransaction {
+
+    pub fn timestamp(&self) -> &str {
+        self.fields.first().map(String::as_str).unwrap_or("")
+    }
+
+    //"12,50" bzw. "-12,50" (Storno) in Cent
+    pub fn price_cents(&self) -> Option<i64> {
+        let text = self.fields.get(1)?.trim();
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, text),
+        };
+        let (euros, cents) = text.split_once(',')?;
+        let euros = euros.parse::<i64>().ok()?;
+        let cents = cents.parse::<i64>().ok().filter(|cents| *cents < 100)?;
+        Some(sign * (euros * 100 + cents))
+    }
+
+    //Storno-Buchung: Zeitstempel;-Preis;Name;Grund;refund;<Nr. der Bestellung>
+    pub fn refunded_order(&self) -> Option<usize> {
+        if self.fields.get(4).map(String::as_str) != Some(REFUND_TYPE) {
+            return None;
+        }
+        self.fields.get(5)?.trim().parse().ok()
+    }
+
+    //Datum als Präfix des Zeitstempels: "2025", "2025-01" oder "2025-01-31"
+    pub fn matches_date(&self, date: &str) -> bool {
+        self.timestamp().starts_with(date)
+    }
+
+    //Freitextsuche ohne Groß-/Kleinschreibung über alle Felder außer Zeitstempel und Preis
+    pub fn matches_text(&self, text: &str) -> bool {
+        let needle = text.to_lowercase();
+        self.fields.iter()
+            .skip(2)
+            .any(|field| field.to_lowercase().contains(&needle))
+    }
+}
+
+pub fn parse_transaction_log(content: &str) -> Vec<LoggedTransaction> {
+    content.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| LoggedTransaction {
+            number: i + 1,
+            line: line.to_string(),
+            fields: split_transaction_fields(line),
+        })
+        .collect()
+}
+
+fn format_eur_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+    format!("{sign}{},{:02}", cents / 100, cents % 100)
+}
+
+//Nummer der Storno-Buchung zu einer Bestellung
+pub fn find_refund(transactions: &[LoggedTransaction], number: usize) -> Option<usize> {
+    transactions.iter()
+        .find(|t| t.refunded_order() == Some(number))
+        .map(|t| t.number)
+}
+
+//Gegenbuchung zur Bestellung `number` mit negativem Betrag; eine Bestellung kann nur einmal storniert werden
+pub fn format_refund_line(
+    transactions: &[LoggedTransaction],
+    number: usize,
+    reason: &str,
+    timestamp: &str,
+) -> Result<String, RefundError> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(RefundError::MissingReason);
+    }
+    let original = transactions.iter()
+        .find(|t| t.number == number)
+        .ok_or(RefundError::UnknownOrder { number })?;
+    if original.refunded_order().is_some() {
+        return Err(RefundError::RefundOfRefund { number });
+    }
+    if let Some(refund) = find_refund(transactions, number) {
+        return Err(RefundError::AlreadyRefunded { number, refund });
+    }
+
+    let price = original.price_cents().ok_or(RefundError::InvalidPrice { number })?;
+    let name = original.fields.get(2).map(String::as_str).unwrap_or("");
+    Ok(format!(
+        "{timestamp};{};{};{};{REFUND_TYPE};{number}",
+        format_eur_cents(-price),
+        escape_transaction_field(&format!("Storno #{number}: {name}")),
+        escape_transaction_field(reason),
+    ))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HistoryPage<'a> {
+    pub total: usize,           //Anzahl aller Treffer
+    pub entries: Vec<&'a LoggedTransaction>,
+}
+
+//neueste zuerst; page beginnt bei 1
+pub fn history_page<'a>(
+    transactions: &'a [LoggedTransaction],
+    date: Option<&str>,
+    text: Option<&str>,
+    page: usize,
+    page_size: usize,
+) -> HistoryPage<'a> {
+    let matching: Vec<&LoggedTransaction> = transactions.iter()
+        .rev()
+        .filter(|t| date.is_none_or(|date| t.matches_date(date)))
+        .filter(|t| text.is_none_or(|text| t.matches_text(text)))
+        .collect();
+
+    //zu große Seitenzahl wie eine Seite hinter dem Ende behandeln: leere Seite
+    let entries = match page.saturating_sub(1).checked_mul(page_size) {
+        Some(skip) => matching.iter().skip(skip).take(page_size).copied().collect(),
+        None => Vec::new(),
+    };
+
+    HistoryPage { total: matching.len(), entries }
+}
+
+impl HistoryPage<'_> {
+
+    // Format: erste Zeile Trefferanzahl, danach "<Nr>;<Storno-Nr. oder leer>;<Transaktionszeile>"
+    pub fn to_body(&self, transactions: &[LoggedTransaction]) -> String {
+        let mut body = self.total.to_string();
+        for entry in &self.entries {
+            let refund = find_refund(transactions, entry.number)
+                .map(|refund| refund.to_string())
+                .unwrap_or_default();
+            body.push('\n');
+            body.push_str(&format!("{};{refund};{}", entry.number, entry.line));
+        }
+        body
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct DailySales {
+    pub date: String,
+    pub orders: usize,
+    pub gross_cents: i64,
+    pub refunds: usize,
+    pub refund_cents: i64,      //positiv, wird vom Umsatz abgezogen
+}
+
+impl DailySales {
+
+    pub fn net_cents(&self) -> i64 {
+        self.gross_cents - self.refund_cents
+    }
+
+    fn add(&mut self, transaction: &LoggedTransaction, cents: i64) {
+        if transaction.refunded_order().is_some() {
+            self.refunds += 1;
+            self.refund_cents -= cents;
+        } else {
+            self.orders += 1;
+            self.gross_cents += cents;
+        }
+    }
+
+    // Format: Datum#Bestellungen#Umsatz#Stornos#Stornobetrag#Netto (Beträge in Cent)
+    pub fn to_line(&self) -> String {
+        format!("{}#{}#{}#{}#{}#{}", self.date, self.orders, self.gross_cents, self.refunds, self.refund_cents, self.net_cents())
+    }
+}
+
+//Umsatz je Tag (aufsteigend) und Gesamtsumme; Stornos zählen an ihrem Buchungstag.
+//Eine Buchung ohne lesbaren Preis macht den Bericht ungültig statt mit 0 gezählt zu werden
+pub fn sales_report(transactions: &[LoggedTransaction], date: Option<&str>) -> Result<(Vec<DailySales>, DailySales), String> {
+    let mut days: Vec<DailySales> = Vec::new();
+    let mut total = DailySales { date: String::from("total"), ..Default::default() };
+
+    for transaction in transactions.iter().filter(|t| date.is_none_or(|date| t.matches_date(date))) {
+        let cents = transaction.price_cents().ok_or_else(|| format!(
+            "Bestellung #{}: Ungültiger Preis '{}'",
+            transaction.number,
+            transaction.fields.get(1).map(String::as_str).unwrap_or(""),
+        ))?;
+        let day = transaction.timestamp().get(..10).unwrap_or(transaction.timestamp());
+        let index = match days.iter().position(|d| d.date == day) {
+            Some(index) => index,
+            None => {
+                days.push(DailySales { date: day.to_string(), ..Default::default() });
+                days.len() - 1
+            }
+        };
+        days[index].add(transaction, cents);
+        total.add(transaction, cents);
+    }
+
+    days.sort_by(|a, b| a.date.cmp(&b.date));
+    Ok((days, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "2025-01-01 12:00:00;15,00;Hawaii\n\
+                       \n\
+                       2025-01-02 18:30:00;21,00;2x Margherita;Klingeln\\; bitte;delivery;Müller;0176 1;Hauptstr. 1\n\
+                       2025-01-02 19:00:00;3,00;Cola\n";
+
+    #[test]
+    fn test_parse_transaction_log_numbers_non_empty_lines() {
+        let transactions = parse_transaction_log(LOG);
+
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions[1].number, 2);
+        assert_eq!(transactions[1].fields[3], "Klingeln; bitte");
+        assert_eq!(transactions[2].number, 3);
+    }
+
+    #[test]
+    fn test_history_page_filters_and_pages_newest_first() {
+        let transactions = parse_transaction_log(LOG);
+
+        let page = history_page(&transactions, None, None, 1, 2);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.entries.iter().map(|t| t.number).collect::<Vec<_>>(), vec![3, 2]);
+
+        let page = history_page(&transactions, None, None, 2, 2);
+        assert_eq!(page.entries.iter().map(|t| t.number).collect::<Vec<_>>(), vec![1]);
+
+        let page = history_page(&transactions, Some("2025-01-02"), Some("müller"), 1, 20);
+        assert_eq!(page.total, 1);
+        assert!(page.to_body(&transactions).starts_with("1\n2;;2025-01-02 18:30:00;21,00;"));
+
+        //Preis wird bei der Textsuche nicht durchsucht
+        assert_eq!(history_page(&transactions, None, Some("15"), 1, 20).total, 0);
+
+        let page = history_page(&transactions, None, None, usize::MAX, 20);
+        assert!(page.entries.is_empty());
+        assert_eq!(page.total, 3);
+    }
+
+    #[test]
+    fn test_refund_line_links_original_order() {
+        let mut transactions = parse_transaction_log(LOG);
+
+        let line = format_refund_line(&transactions, 2, "Falsch; getippt", "2025-01-03 09:00:00").unwrap();
+        assert_eq!(line, "2025-01-03 09:00:00;-21,00;Storno #2: 2x Margherita;Falsch\\; getippt;refund;2");
+
+        transactions = parse_transaction_log(&format!("{LOG}{line}\n"));
+        assert_eq!(transactions[3].price_cents(), Some(-2100));
+        assert_eq!(transactions[3].refunded_order(), Some(2));
+        assert_eq!(find_refund(&transactions, 2), Some(4));
+
+        assert_eq!(format_refund_line(&transactions, 2, "nochmal", ""), Err(RefundError::AlreadyRefunded { number: 2, refund: 4 }));
+        assert_eq!(format_refund_line(&transactions, 4, "Storno", ""), Err(RefundError::RefundOfRefund { number: 4 }));
+        assert_eq!(format_refund_line(&transactions, 9, "weg", ""), Err(RefundError::UnknownOrder { number: 9 }));
+        assert_eq!(format_refund_line(&transactions, 1, " ", ""), Err(RefundError::MissingReason));
+
+        let transactions = parse_transaction_log("2025-01-01 12:00:00;15;Hawaii\n");
+        assert_eq!(format_refund_line(&transactions, 1, "weg", ""), Err(RefundError::InvalidPrice { number: 1 }));
+    }
+
+    #[test]
+    fn test_sales_report_nets_out_refunds() {
+        let log = format!("{LOG}2025-01-03 09:00:00;-21,00;Storno #2: 2x Margherita;Falsch;refund;2\n");
+        let transactions = parse_transaction_log(&log);
+
+        let (days, total) = sales_report(&transactions, None).unwrap();
+        assert_eq!(days.iter().map(|d| d.date.as_str()).collect::<Vec<_>>(), vec!["2025-01-01", "2025-01-02", "2025-01-03"]);
+        assert_eq!(days[1].net_cents(), 2400);
+        assert_eq!(days[2].to_line(), "2025-01-03#0#0#1#2100#-2100");
+        assert_eq!(total.to_line(), "total#3#3900#1#2100#1800");
+
+        let (days, _) = sales_report(&transactions, Some("2025-01-02")).unwrap();
+        assert_eq!(days.len(), 1);
+
+        let transactions = parse_transaction_log(&format!("{LOG}2025-01-04 10:00:00;abc;Cola\n"));
+        assert_eq!(sales_report(&transactions, None), Err(String::from("Bestellung #4: Ungültiger Preis 'abc'")));
+        assert!(sales_report(&transactions, Some("2025-01-02")).is_ok());
+    }
+}
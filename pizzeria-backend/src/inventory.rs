@@ -0,0 +1,230 @@
+//Zutatenlager (Gramm oder Stück) mit Rezepten je Topping und Warnschwellen
+use crate::catalog::read_optional;
+use crate::custom_error::PricingError;
+
+pub const INVENTORY_FILE: &str = "inventory_text";
+pub const RECIPES_FILE: &str = "recipes_text";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Unit {
+    Grams,
+    Units,
+}
+
+impl Unit {
+    pub fn parse(text: &str) -> Option<Unit> {
+        match text.trim() {
+            "g" => Some(Unit::Grams),
+            "units" => Some(Unit::Units),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Unit::Grams => "g",
+            Unit::Units => "units",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ingredient {
+    pub name: String,
+    pub amount: u32,
+    pub unit: Unit,
+    pub threshold: u32,
+}
+
+impl Ingredient {
+    pub fn is_low(&self) -> bool {
+        self.amount < self.threshold
+    }
+
+    pub fn to_line(&self) -> String {
+        format!("{}#{}#{}#{}", self.name, self.amount, self.unit.code(), self.threshold)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Inventory {
+    pub ingredients: Vec<Ingredient>,
+}
+
+impl Inventory {
+    pub fn ingredient(&self, name: &str) -> Option<&Ingredient> {
+        self.ingredients.iter().find(|ingredient| ingredient.name == name)
+    }
+
+    //Lager ist eine Schätzung: Verbrauch sperrt keine Bestellung, der Bestand fällt höchstens auf 0
+    pub fn consume(&mut self, usage: &[(String, u32)]) {
+        for (name, amount) in usage {
+            if let Some(ingredient) = self.ingredients.iter_mut().find(|ingredient| ingredient.name == *name) {
+                ingredient.amount = ingredient.amount.saturating_sub(*amount);
+            }
+        }
+    }
+
+    pub fn alerts(&self) -> Vec<&Ingredient> {
+        self.ingredients.iter().filter(|ingredient| ingredient.is_low()).collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        self.ingredients.iter()
+            .map(|ingredient| format!("{}\n", ingredient.to_line()))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Recipe {
+    pub topping: String,
+    pub parts: Vec<(String, u32)>,
+}
+
+// Format: <Zutat>#<Bestand>#<g|units>#<Warnschwelle>
+pub fn parse_inventory(content: &str) -> Result<Inventory, String> {
+    let mut inventory = Inventory::default();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split('#').map(str::trim).collect();
+        let [name, amount_text, unit_text, threshold_text] = parts[..] else {
+            return Err(format!("Zeile {}: erwartet 'Zutat#Bestand#Einheit#Warnschwelle'", lineno + 1));
+        };
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Zutat fehlt", lineno + 1));
+        }
+        let amount = amount_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Bestand '{}'", lineno + 1, amount_text))?;
+        let unit = Unit::parse(unit_text)
+            .ok_or_else(|| format!("Zeile {}: Unbekannte Einheit '{}' (g oder units)", lineno + 1, unit_text))?;
+        let threshold = threshold_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültige Warnschwelle '{}'", lineno + 1, threshold_text))?;
+
+        inventory.ingredients.push(Ingredient { name: name.to_string(), amount, unit, threshold });
+    }
+
+    Ok(inventory)
+}
+
+// Format: <Topping>#<Zutat>:<Menge>|<Zutat>:<Menge>|… (Menge in der Einheit der Zutat)
+pub fn parse_recipes(content: &str, inventory: &Inventory) -> Result<Vec<Recipe>, String> {
+    let mut recipes = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (topping, list) = line.split_once('#')
+            .ok_or_else(|| format!("Zeile {}: erwartet 'Topping#Zutat:Menge|…'", lineno + 1))?;
+        let mut parts = Vec::new();
+        for part in list.split('|').map(str::trim).filter(|part| !part.is_empty()) {
+            let (name, amount_text) = part.split_once(':')
+                .ok_or_else(|| format!("Zeile {}: erwartet 'Zutat:Menge', erhalten '{}'", lineno + 1, part))?;
+            let name = name.trim();
+            if inventory.ingredient(name).is_none() {
+                return Err(format!("Zeile {}: Unbekannte Zutat '{}'", lineno + 1, name));
+            }
+            let amount = amount_text.trim().parse::<u32>()
+                .map_err(|_| format!("Zeile {}: Ungültige Menge '{}'", lineno + 1, amount_text.trim()))?;
+            parts.push((name.to_string(), amount));
+        }
+
+        recipes.push(Recipe { topping: topping.trim().to_string(), parts });
+    }
+
+    Ok(recipes)
+}
+
+//Zutatenverbrauch aus dem Toppingverbrauch einer Bestellung (siehe stock::topping_usage);
+//ein Überlauf lehnt die Bestellung ab
+pub fn ingredient_usage(recipes: &[Recipe], toppings: &[(String, u32)]) -> Result<Vec<(String, u32)>, PricingError> {
+    let mut usage: Vec<(String, u32)> = Vec::new();
+
+    for (topping, portions) in toppings {
+        let Some(recipe) = recipes.iter().find(|recipe| recipe.topping == *topping) else {
+            continue;
+        };
+        for (name, amount) in &recipe.parts {
+            let too_large = || PricingError::UsageTooLarge { name: name.clone() };
+            let needed = amount.checked_mul(*portions).ok_or_else(too_large)?;
+            match usage.iter_mut().find(|(used, _)| used == name) {
+                Some((_, total)) => *total = total.checked_add(needed).ok_or_else(too_large)?,
+                None => usage.push((name.clone(), needed)),
+            }
+        }
+    }
+
+    Ok(usage)
+}
+
+pub async fn load_inventory() -> Result<(Inventory, Vec<Recipe>), String> {
+    let inventory = read_optional(INVENTORY_FILE).await
+        .map_err(|error| format!("{INVENTORY_FILE}: {error}"))?;
+    let recipes = read_optional(RECIPES_FILE).await
+        .map_err(|error| format!("{RECIPES_FILE}: {error}"))?;
+
+    let inventory = parse_inventory(&inventory).map_err(|error| format!("{INVENTORY_FILE}: {error}"))?;
+    let recipes = parse_recipes(&recipes, &inventory).map_err(|error| format!("{RECIPES_FILE}: {error}"))?;
+    Ok((inventory, recipes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory() -> Inventory {
+        parse_inventory("Mozzarella#1000#g#400\nHam Slices#20#units#10\nMushrooms#500#g#100").unwrap()
+    }
+
+    #[test]
+    fn test_recipes_consume_ingredients() {
+        let mut inventory = inventory();
+        let recipes = parse_recipes("Xtra Cheese#Mozzarella:150\nHam#Ham Slices:4|Mozzarella:20", &inventory).unwrap();
+
+        let usage = ingredient_usage(&recipes, &[
+            (String::from("Ham"), 3),
+            (String::from("Xtra Cheese"), 4),
+            (String::from("Corn"), 2),
+        ]).unwrap();
+        assert_eq!(usage, vec![(String::from("Ham Slices"), 12), (String::from("Mozzarella"), 660)]);
+
+        inventory.consume(&usage);
+        assert_eq!(inventory.alerts().iter().map(|ingredient| ingredient.to_line()).collect::<Vec<_>>(), vec![
+            String::from("Mozzarella#340#g#400"),
+            String::from("Ham Slices#8#units#10"),
+        ]);
+
+        inventory.consume(&usage);
+        assert_eq!(inventory.ingredient("Ham Slices").map(|ingredient| ingredient.amount), Some(0));
+    }
+
+    #[test]
+    fn test_reject_ingredient_usage_overflow() {
+        let recipes = parse_recipes("Xtra Cheese#Mozzarella:150\nHam#Ham Slices:4|Mozzarella:20", &inventory()).unwrap();
+
+        assert!(matches!(
+            ingredient_usage(&recipes, &[(String::from("Xtra Cheese"), u32::MAX / 100)]).unwrap_err(),
+            PricingError::UsageTooLarge { name } if name == "Mozzarella"
+        ));
+        assert!(matches!(
+            ingredient_usage(&recipes, &[(String::from("Xtra Cheese"), u32::MAX / 150), (String::from("Ham"), 3)]).unwrap_err(),
+            PricingError::UsageTooLarge { name } if name == "Mozzarella"
+        ));
+    }
+
+    #[test]
+    fn test_reject_invalid_inventory_and_recipes() {
+        assert!(parse_inventory("Flour#10#kg#2").is_err());
+        assert!(parse_inventory("Flour#10#g").is_err());
+        assert!(parse_recipes("Corn#Sweetcorn:50", &inventory()).is_err());
+        assert!(parse_recipes("Ham#Ham Slices", &inventory()).is_err());
+    }
+}
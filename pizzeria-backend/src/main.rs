@@ -1,15 +1,28 @@
 mod config;
 mod custom_error;
+mod catalog;
+mod pricing;
+mod history;
+mod stock;
+mod inventory;
 
 use tokio::fs;
 use std::path::Path;
-use axum::extract::Query;
+use axum::extract::{Path as UrlPath, Query};
 use axum::http::StatusCode;
 use axum::Router;
 use axum::routing::{delete, get, post};
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
 use serde::Deserialize;
+use crate::catalog::{load_catalog, parse_delivery_settings, read_optional, FoodInfo, BUNDLES_FILE, DELIVERY_FILE, ITEMS_FILE, LIMITS_FILE, PREBUILDS_FILE, TOPPINGS_FILE};
+use crate::custom_error::{PricingError, RefundError};
+use crate::history::{format_refund_line, history_page, parse_transaction_log, sales_report, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::stock::{is_booked, parse_restock, split_booking, topping_usage, BOOKINGS_FILE, STOCK_FILE};
+use crate::inventory::{ingredient_usage, load_inventory, INVENTORY_FILE};
+use crate::pricing::{active_rule, now_local, parse_pricing_rules, price_order, PricingRule, PRICING_RULES_FILE};
+use pizzeria_shared::transaction_log::now_local_timestamp;
 
 #[tokio::main]
 async fn main() {
@@ -17,8 +30,22 @@ async fn main() {
     let app = Router::new()
         .route("/", get(root))
         .route("/transaction", post(store_transaction))
+        .route("/transactions", get(get_transactions))
+        .route("/transactions/{number}/refund", post(refund_transaction))
+        .route("/reports/sales", get(get_sales_report))
+        .route("/items", get(get_items))
+        .route("/bundles", get(get_bundles))
+        .route("/pricing", get(get_active_pricing_rule))
+        .route("/delivery", get(get_delivery_settings))
+        .route("/order/price", post(price_order_request))
         .route("/toppings", get(get_toppings).post(add_topping).delete(delete_topping))
-        .route("/toppings/clear", delete(clear_topping_list));
+        .route("/toppings/clear", delete(clear_topping_list))
+        .route("/toppings/limits", get(get_topping_limits))
+        .route("/stock", get(get_stock))
+        .route("/stock/consume", post(consume_stock))
+        .route("/stock/restock", post(restock_toppings))
+        .route("/inventory", get(get_inventory))
+        .route("/inventory/alerts", get(get_inventory_alerts));
 
     let address = match config::get_socket_address() {
         Ok(a) => a,
@@ -38,7 +65,7 @@ async fn main() {
 
 async fn root() -> (StatusCode, String) {
     eprintln!("Received request for Order Menu.");
-    let path = Path::new("pizza_prebuilds_text");
+    let path = Path::new(PREBUILDS_FILE);
 
     match fs::read_to_string(path).await {
         Ok(prebuilds) => {
@@ -52,32 +79,422 @@ async fn root() -> (StatusCode, String) {
 }
 
 const LOG_PATH: &str = "transactions.log";
-const TOPPINGS_FILE: &str = "toppings_text";
 
-async fn store_transaction(mut transaction_record: String) -> StatusCode {
+//serialisiert Schreibzugriffe, damit jede Bestellung eine eindeutige Nummer erhält
+static LOG_LOCK: Mutex<()> = Mutex::const_new(());
+
+//Antwort: Bestellnummer = Zeilennummer im Transaktionslog
+async fn store_transaction(mut transaction_record: String) -> (StatusCode, String) {
     eprintln!("Received request to store transaction record '{transaction_record}'.");
 
     if !transaction_record.ends_with('\n') {
         transaction_record.push('\n');
     }
 
-    match OpenOptions::new()
+    let _guard = LOG_LOCK.lock().await;
+    let order_number = match read_optional(LOG_PATH).await {
+        Ok(log) => log.lines().filter(|line| !line.trim().is_empty()).count() + 1,
+        Err(e) => {
+            eprintln!("read error: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+
+    match append_to_log(&transaction_record).await {
+        Ok(()) => (StatusCode::OK, order_number.to_string()),
+        Err(e) => {
+            eprintln!("write error: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+//Aufrufer hält LOG_LOCK
+async fn append_to_log(record: &str) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
         .create(true)
         .append(true)
         .open(LOG_PATH)
-        .await
-    {
-        Ok(mut file) => {
-            if let Err(e) = file.write_all(transaction_record.as_bytes()).await {
-                eprintln!("write error: {e}");
-                StatusCode::INTERNAL_SERVER_ERROR
-            } else {
-                StatusCode::NO_CONTENT
-            }
+        .await?;
+    file.write_all(record.as_bytes()).await
+}
+
+//Body = Storno-Grund; Antwort = Nummer der Storno-Buchung
+async fn refund_transaction(UrlPath(number): UrlPath<usize>, reason: String) -> (StatusCode, String) {
+    eprintln!("Received request to refund order #{number}: '{reason}'.");
+
+    let _guard = LOG_LOCK.lock().await;
+    let transactions = match read_optional(LOG_PATH).await {
+        Ok(log) => parse_transaction_log(&log),
+        Err(e) => {
+            eprintln!("read error: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+
+    let line = match format_refund_line(&transactions, number, &reason, &now_local_timestamp()) {
+        Ok(line) => line,
+        Err(error) => {
+            eprintln!("refund: {error}");
+            let status = match error {
+                RefundError::UnknownOrder { .. } => StatusCode::NOT_FOUND,
+                RefundError::MissingReason | RefundError::InvalidPrice { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                RefundError::RefundOfRefund { .. } | RefundError::AlreadyRefunded { .. } => StatusCode::CONFLICT,
+            };
+            return (status, error.to_string());
         }
+    };
+
+    match append_to_log(&format!("{line}\n")).await {
+        Ok(()) => (StatusCode::OK, (transactions.len() + 1).to_string()),
         Err(e) => {
-            eprintln!("open error: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
+            eprintln!("write error: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HistoryParameters {
+    page: Option<usize>,
+    per_page: Option<usize>,
+    date: Option<String>,
+    q: Option<String>,
+}
+
+async fn get_transactions(Query(p): Query<HistoryParameters>) -> (StatusCode, String) {
+    eprintln!("Received request for Transaction History.");
+
+    let page = p.page.unwrap_or(1);
+    let per_page = p.per_page.unwrap_or(DEFAULT_PAGE_SIZE);
+    if page == 0 || per_page == 0 || per_page > MAX_PAGE_SIZE {
+        return (StatusCode::BAD_REQUEST, format!("page >= 1 und per_page 1..={MAX_PAGE_SIZE} erwartet"));
+    }
+    // leere Filter wie fehlende behandeln
+    let date = p.date.as_deref().map(str::trim).filter(|date| !date.is_empty());
+    let text = p.q.as_deref().map(str::trim).filter(|text| !text.is_empty());
+
+    let _guard = LOG_LOCK.lock().await;
+    match read_optional(LOG_PATH).await {
+        Ok(log) => {
+            let transactions = parse_transaction_log(&log);
+            let history = history_page(&transactions, date, text, page, per_page);
+            (StatusCode::OK, history.to_body(&transactions))
+        }
+        Err(error) => {
+            eprintln!("Error while reading file {LOG_PATH:?}: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ReportParameters { date: Option<String> }
+
+async fn get_sales_report(Query(p): Query<ReportParameters>) -> (StatusCode, String) {
+    eprintln!("Received request for Sales Report.");
+
+    let date = p.date.as_deref().map(str::trim).filter(|date| !date.is_empty());
+
+    let _guard = LOG_LOCK.lock().await;
+    match read_optional(LOG_PATH).await {
+        Ok(log) => {
+            let transactions = parse_transaction_log(&log);
+            match sales_report(&transactions, date) {
+                Ok((days, total)) => {
+                    let body = days.iter()
+                        .chain(std::iter::once(&total))
+                        .map(|day| day.to_line())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    (StatusCode::OK, body)
+                }
+                Err(error) => {
+                    eprintln!("Invalid file {LOG_PATH:?}: {error}");
+                    (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+                }
+            }
+        }
+        Err(error) => {
+            eprintln!("Error while reading file {LOG_PATH:?}: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+async fn get_items() -> (StatusCode, String) {
+    eprintln!("Received request for Item List.");
+
+    match read_optional(ITEMS_FILE).await {
+        Ok(items) => (StatusCode::OK, items),
+        Err(error) => {
+            eprintln!("Error while reading file {ITEMS_FILE:?}: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+async fn get_topping_limits() -> (StatusCode, String) {
+    eprintln!("Received request for Topping Limits.");
+
+    match read_optional(LIMITS_FILE).await {
+        Ok(limits) => (StatusCode::OK, limits),
+        Err(error) => {
+            eprintln!("Error while reading file {LIMITS_FILE:?}: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+async fn get_bundles() -> (StatusCode, String) {
+    eprintln!("Received request for Bundle List.");
+
+    match read_optional(BUNDLES_FILE).await {
+        Ok(bundles) => (StatusCode::OK, bundles),
+        Err(error) => {
+            eprintln!("Error while reading file {BUNDLES_FILE:?}: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+async fn get_delivery_settings() -> (StatusCode, String) {
+    eprintln!("Received request for Delivery Settings.");
+
+    let settings = match read_optional(DELIVERY_FILE).await {
+        Ok(settings) => settings,
+        Err(error) => {
+            eprintln!("Error while reading file {DELIVERY_FILE:?}: {error}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+    // fehlerhafte Datei nicht ausliefern, das Frontend würde sie sonst erst beim Bestellen ablehnen
+    if let Err(error) = parse_delivery_settings(&settings) {
+        eprintln!("Invalid file {DELIVERY_FILE:?}: {error}");
+        return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+    }
+    (StatusCode::OK, settings)
+}
+
+//serialisiert Bestandsänderungen (Verbrauch und Nachfüllen)
+static STOCK_LOCK: Mutex<()> = Mutex::const_new(());
+
+async fn get_stock() -> (StatusCode, String) {
+    eprintln!("Received request for Topping Stock.");
+
+    match read_optional(STOCK_FILE).await {
+        Ok(stock) => (StatusCode::OK, stock),
+        Err(error) => {
+            eprintln!("Error while reading file {STOCK_FILE:?}: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+//Body = optionale Zeile "booking#<Kennung>" + Bestellzeilen wie bei /order/price; zieht die Toppings vom Bestand ab.
+//Eine bereits gebuchte Kennung ändert nichts mehr, so kann der Client nach verlorener Antwort erneut senden
+async fn consume_stock(body: String) -> (StatusCode, String) {
+    eprintln!("Received request to consume stock for '{body}'.");
+
+    let (booking, order) = split_booking(&body);
+    let _guard = STOCK_LOCK.lock().await;
+    let mut catalog = match load_catalog().await {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            eprintln!("Error while loading catalog: {error}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+
+    if let Some(key) = booking {
+        match read_optional(BOOKINGS_FILE).await {
+            Ok(bookings) if is_booked(&bookings, key) => {
+                eprintln!("consume_stock: booking '{key}' already applied");
+                return (StatusCode::OK, catalog.stock.to_text());
+            }
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("Error while reading file {BOOKINGS_FILE:?}: {error}");
+                return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+            }
+        }
+    }
+
+    let (mut inventory, recipes) = match load_inventory().await {
+        Ok(inventory) => inventory,
+        Err(error) => {
+            eprintln!("Error while loading inventory: {error}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+
+    let usage = topping_usage(&catalog, order)
+        .and_then(|usage| catalog.stock.consume(&usage).map(|()| usage))
+        .and_then(|usage| ingredient_usage(&recipes, &usage));
+    match usage {
+        Ok(usage) => inventory.consume(&usage),
+        Err(error @ PricingError::OutOfStock { .. }) => {
+            eprintln!("consume_stock: {error}");
+            return (StatusCode::CONFLICT, error.to_string());
+        }
+        Err(error) => {
+            eprintln!("consume_stock: {error}");
+            return (StatusCode::UNPROCESSABLE_ENTITY, error.to_string());
+        }
+    }
+
+    if let Err(error) = fs::write(STOCK_FILE, catalog.stock.to_text()).await {
+        eprintln!("write error: {error}");
+        return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+    }
+    //ohne Zutatenlager bleibt die Datei unangetastet
+    if !inventory.ingredients.is_empty()
+        && let Err(error) = fs::write(INVENTORY_FILE, inventory.to_text()).await {
+        eprintln!("write error: {error}");
+        return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+    }
+    if let Some(key) = booking
+        && let Err(error) = append_booking(key).await {
+        eprintln!("write error: {error}");
+        return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+    }
+    (StatusCode::OK, catalog.stock.to_text())
+}
+
+//Aufrufer hält STOCK_LOCK
+async fn append_booking(key: &str) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(BOOKINGS_FILE)
+        .await?;
+    file.write_all(format!("{key}\n").as_bytes()).await
+}
+
+//Body: <Topping>#<Menge> je Zeile; Antwort = neuer Bestand
+async fn restock_toppings(body: String) -> (StatusCode, String) {
+    eprintln!("Received request to restock '{body}'.");
+
+    let deliveries = match parse_restock(&body) {
+        Ok(deliveries) if !deliveries.is_empty() => deliveries,
+        Ok(_) => return (StatusCode::BAD_REQUEST, String::from("Keine Nachlieferung angegeben")),
+        Err(error) => {
+            eprintln!("restock_toppings: {error}");
+            return (StatusCode::BAD_REQUEST, error);
+        }
+    };
+
+    let _guard = STOCK_LOCK.lock().await;
+    let mut catalog = match load_catalog().await {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            eprintln!("Error while loading catalog: {error}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+
+    for (name, amount) in &deliveries {
+        if catalog.topping(name).is_none() {
+            let error = PricingError::UnknownTopping { name: name.clone() };
+            eprintln!("restock_toppings: {error}");
+            return (StatusCode::NOT_FOUND, error.to_string());
+        }
+        if let Err(error) = catalog.stock.restock(name, *amount) {
+            eprintln!("restock_toppings: {error}");
+            return (StatusCode::BAD_REQUEST, error);
+        }
+    }
+
+    match fs::write(STOCK_FILE, catalog.stock.to_text()).await {
+        Ok(()) => (StatusCode::OK, catalog.stock.to_text()),
+        Err(error) => {
+            eprintln!("write error: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+async fn get_inventory() -> (StatusCode, String) {
+    eprintln!("Received request for Ingredient Inventory.");
+
+    match read_optional(INVENTORY_FILE).await {
+        Ok(inventory) => (StatusCode::OK, inventory),
+        Err(error) => {
+            eprintln!("Error while reading file {INVENTORY_FILE:?}: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+//Antwort: Zutaten unter der Warnschwelle, Format wie im Zutatenlager
+async fn get_inventory_alerts() -> (StatusCode, String) {
+    eprintln!("Received request for Inventory Alerts.");
+
+    match load_inventory().await {
+        Ok((inventory, _)) => {
+            let body = inventory.alerts().iter()
+                .map(|ingredient| format!("{}\n", ingredient.to_line()))
+                .collect();
+            (StatusCode::OK, body)
+        }
+        Err(error) => {
+            eprintln!("Error while loading inventory: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+async fn load_active_pricing_rule() -> Result<Option<PricingRule>, String> {
+    let content = read_optional(PRICING_RULES_FILE).await
+        .map_err(|error| format!("{PRICING_RULES_FILE}: {error}"))?;
+    let rules = parse_pricing_rules(&content)
+        .map_err(|error| format!("{PRICING_RULES_FILE}: {error}"))?;
+    let (weekday, minute) = now_local();
+    Ok(active_rule(&rules, weekday, minute).cloned())
+}
+
+async fn get_active_pricing_rule() -> (StatusCode, String) {
+    eprintln!("Received request for active Pricing Rule.");
+
+    match load_active_pricing_rule().await {
+        Ok(Some(rule)) => (StatusCode::OK, rule.to_line()),
+        Ok(None) => (StatusCode::OK, String::new()),
+        Err(error) => {
+            eprintln!("Error while loading pricing rules: {error}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+async fn price_order_request(order: String) -> (StatusCode, String) {
+    eprintln!("Received request to price order '{order}'.");
+
+    let catalog = match load_catalog().await {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            eprintln!("Error while loading catalog: {error}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+    let rule = match load_active_pricing_rule().await {
+        Ok(rule) => rule,
+        Err(error) => {
+            eprintln!("Error while loading pricing rules: {error}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    };
+
+    match price_order(&catalog, rule.as_ref(), &order) {
+        Ok(prices) => {
+            let body = prices.iter()
+                .map(|cents| cents.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            (StatusCode::OK, body)
+        }
+        Err(error) => {
+            eprintln!("price_order: {error}");
+            (StatusCode::UNPROCESSABLE_ENTITY, error.to_string())
         }
     }
 }
@@ -101,10 +518,16 @@ async fn add_topping(body: String) -> StatusCode {
 
     let line = body.lines().next().unwrap_or("").trim();
 
-    let Some((name_raw, price_raw)) = line.split_once('#') else {
-        eprintln!("add_topping: invalid format (expected 'Name#Price'), got {:?}", line);
+    let mut parts = line.splitn(3, '#');
+    let (Some(name_raw), Some(price_raw)) = (parts.next(), parts.next()) else {
+        eprintln!("add_topping: invalid format (expected 'Name#Price[#Tags]'), got {:?}", line);
         return StatusCode::BAD_REQUEST;
     };
+    let tags = parts.next().unwrap_or("").trim();
+    if let Err(e) = FoodInfo::parse(tags) {
+        eprintln!("add_topping: {e}");
+        return StatusCode::BAD_REQUEST;
+    }
 
     let name = name_raw.trim();
 
@@ -121,7 +544,11 @@ async fn add_topping(body: String) -> StatusCode {
         }
     };
 
-    let to_write = format!("{name}#{price}\n");
+    let to_write = if tags.is_empty() {
+        format!("{name}#{price}\n")
+    } else {
+        format!("{name}#{price}#{tags}\n")
+    };
     println!("Received request to ADD Topping '{}'.", name);
 
     match OpenOptions::new().create(true).append(true).open(TOPPINGS_FILE).await {
@@ -0,0 +1,554 @@
+use std::ptr;
+use libc::{localtime_r, time, time_t, tm};
+use crate::catalog::Catalog;
+use crate::custom_error::PricingError;
+use crate::stock::topping_usage;
+
+pub const PRICING_RULES_FILE: &str = "pricing_rules_text";
+pub const CUSTOM_BASE_PRICE: u32 = 6;
+//Toppings auf nur einer Hälfte kosten die Hälfte
+pub const HALF_TOPPING_PERCENT: u32 = 50;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Scope {
+    Prebuilt,
+    Custom,
+    Item,
+    All,
+}
+
+impl Scope {
+    fn parse(text: &str) -> Option<Scope> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "prebuilt" => Some(Scope::Prebuilt),
+            "custom" => Some(Scope::Custom),
+            "item" => Some(Scope::Item),
+            "all" | "*" => Some(Scope::All),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Prebuilt => "prebuilt",
+            Scope::Custom => "custom",
+            Scope::Item => "item",
+            Scope::All => "all",
+        }
+    }
+
+    pub fn covers(&self, kind: Scope) -> bool {
+        matches!(self, Scope::All) || *self == kind
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PricingRule {
+    pub name: String,
+    pub days: [bool; 7],        //Index 0 = Montag
+    pub from_minute: u32,
+    pub to_minute: u32,         //exklusiv
+    pub percent: u32,
+    pub scope: Scope,
+}
+
+impl PricingRule {
+    pub fn is_active(&self, weekday: usize, minute: u32) -> bool {
+        let day_listed = |day: usize| self.days.get(day % 7).copied().unwrap_or(false);
+        if self.from_minute <= self.to_minute {
+            day_listed(weekday) && (self.from_minute..self.to_minute).contains(&minute)
+        } else if minute >= self.from_minute {
+            // über Mitternacht, z.B. 22:00-02:00: der Abend gehört zum genannten Tag,
+            day_listed(weekday)
+        } else {
+            // die Zeit nach Mitternacht zum Vortag (Fr 22:00-02:00 gilt Sa 00:30)
+            minute < self.to_minute && day_listed(weekday + 6)
+        }
+    }
+
+    pub fn apply(&self, cents: u32) -> u32 {
+        cents - cents * self.percent / 100
+    }
+
+    //Antwortformat für das Frontend: <Name>#<Prozent>#<Geltungsbereich>
+    pub fn to_line(&self) -> String {
+        format!("{}#{}#{}", self.name, self.percent, self.scope.as_str())
+    }
+}
+
+// Format: <Name>#<Tage>#<Von>-<Bis>#<Prozent>#<prebuilt|custom|item|all>
+// Tage: "Mon-Fri", "Sat|Sun" oder "*"
+pub fn parse_pricing_rules(content: &str) -> Result<Vec<PricingRule>, String> {
+    let mut rules = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('#').map(str::trim).collect();
+        let [name, days, hours, percent, scope] = fields[..] else {
+            return Err(format!("Zeile {}: erwartet 'Name#Tage#Von-Bis#Prozent#Bereich'", lineno + 1));
+        };
+
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Regel-Name fehlt", lineno + 1));
+        }
+        let days = parse_days(days)
+            .ok_or_else(|| format!("Zeile {}: Ungültige Tage '{}'", lineno + 1, days))?;
+        let (from_minute, to_minute) = hours.split_once('-')
+            .and_then(|(from, to)| Some((parse_clock(from)?, parse_clock(to)?)))
+            .ok_or_else(|| format!("Zeile {}: Ungültige Uhrzeit '{}'", lineno + 1, hours))?;
+        let percent = percent.parse::<u32>().ok()
+            .filter(|percent| *percent <= 100)
+            .ok_or_else(|| format!("Zeile {}: Ungültiger Rabatt '{}'", lineno + 1, percent))?;
+        let scope = Scope::parse(scope)
+            .ok_or_else(|| format!("Zeile {}: Ungültiger Bereich '{}'", lineno + 1, scope))?;
+
+        rules.push(PricingRule {
+            name: name.to_string(),
+            days,
+            from_minute,
+            to_minute,
+            percent,
+            scope,
+        });
+    }
+
+    Ok(rules)
+}
+
+fn parse_weekday(text: &str) -> Option<usize> {
+    WEEKDAYS.iter().position(|day| day.eq_ignore_ascii_case(text.trim()))
+}
+
+fn parse_days(text: &str) -> Option<[bool; 7]> {
+    let mut days = [false; 7];
+    if text == "*" {
+        return Some([true; 7]);
+    }
+    for part in text.split('|') {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let from = parse_weekday(from)?;
+                let to = parse_weekday(to)?;
+                let mut day = from;
+                loop {
+                    days[day] = true;
+                    if day == to { break; }
+                    day = (day + 1) % 7;
+                }
+            }
+            None => days[parse_weekday(part)?] = true,
+        }
+    }
+    Some(days)
+}
+
+fn parse_clock(text: &str) -> Option<u32> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let hours = hours.parse::<u32>().ok()?;
+    let minutes = minutes.parse::<u32>().ok()?;
+    if hours > 24 || minutes > 59 || (hours == 24 && minutes > 0) {
+        return None;
+    }
+    Some(hours * 60 + minutes)
+}
+
+pub fn active_rule(rules: &[PricingRule], weekday: usize, minute: u32) -> Option<&PricingRule> {
+    rules.iter().find(|rule| rule.is_active(weekday, minute))
+}
+
+//Wochentag (0 = Montag) und Minute des Tages über libc
+pub fn now_local() -> (usize, u32) {
+    unsafe {
+        let now_time: time_t = time(ptr::null_mut());
+        let mut local_time: tm = std::mem::zeroed();
+
+        if localtime_r(&now_time, &mut local_time).is_null() {
+            return (0, 0);      //Fallback
+        }
+
+        let weekday = (local_time.tm_wday as usize + 6) % 7;
+        let minute = (local_time.tm_hour * 60 + local_time.tm_min) as u32;
+        (weekday, minute)
+    }
+}
+
+// Bestellzeilen: "prebuilt#<Pizza-Name>", "prebuilt#<Pizza-Name>#<Topping1|Topping2@L|…>" (angepasst),
+// "custom#<Topping1|Topping2@L|Topping3@R|…>", "item#<Artikel-Name>", "delivery#" oder "bundle#<Bundle-Name>#<Auswahl1|Auswahl2|…>"
+// Ergebnis: Preis je Zeile in Cent
+pub fn price_order(catalog: &Catalog, rule: Option<&PricingRule>, order: &str) -> Result<Vec<u32>, PricingError> {
+    let mut prices = Vec::new();
+    let mut delivery = false;
+
+    for line in order.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (kind, rest) = line.split_once('#')
+            .ok_or_else(|| PricingError::InvalidLine { line: line.to_string() })?;
+
+        let (scope, cents) = match kind {
+            "prebuilt" => {
+                let (name, toppings) = match rest.split_once('#') {
+                    Some((name, toppings)) => (name.trim(), Some(toppings)),
+                    None => (rest.trim(), None),
+                };
+                let prebuilt = catalog.prebuilt(name)
+                    .ok_or_else(|| PricingError::UnknownPizza { name: name.to_string() })?;
+                match toppings {
+                    //angepasste Pizza: Basispreis der Vorlage + gewählte Toppings
+                    Some(toppings) => (Scope::Prebuilt, prebuilt.base_price * 100 + price_custom_toppings(catalog, Scope::Prebuilt, toppings)?),
+                    None => {
+                        let toppings = sum_toppings(catalog, prebuilt.toppings.iter().map(String::as_str))?;
+                        (Scope::Prebuilt, (prebuilt.base_price + toppings) * 100)
+                    }
+                }
+            }
+            "custom" => (Scope::Custom, CUSTOM_BASE_PRICE * 100 + price_custom_toppings(catalog, Scope::Custom, rest)?),
+            "item" => {
+                let name = rest.split('#').next().unwrap_or("").trim();
+                let item = catalog.item(name)
+                    .ok_or_else(|| PricingError::UnknownItem { name: name.to_string() })?;
+                (Scope::Item, item.price * 100)
+            }
+            "bundle" => {
+                //Bundles sind bereits rabattiert, Preisregeln gelten hier nicht
+                prices.push(price_bundle(catalog, rest)?);
+                continue;
+            }
+            "delivery" if !delivery => {
+                //Liefergebühr, ohne Rabatt und nicht im Mindestbestellwert enthalten
+                delivery = true;
+                prices.push(catalog.delivery.fee * 100);
+                continue;
+            }
+            _ => return Err(PricingError::InvalidLine { line: line.to_string() }),
+        };
+
+        let cents = match rule {
+            Some(rule) if rule.scope.covers(scope) => rule.apply(cents),
+            _ => cents,
+        };
+        prices.push(cents);
+    }
+
+    if delivery {
+        let fee = catalog.delivery.fee * 100;
+        let goods: u32 = prices.iter().sum::<u32>() - fee;
+        if goods < catalog.delivery.minimum * 100 {
+            return Err(PricingError::BelowDeliveryMinimum { minimum: catalog.delivery.minimum });
+        }
+    }
+
+    //ausverkaufte Toppings sperren auch Prebuilt-Pizzen, die sie enthalten
+    catalog.stock.check(&topping_usage(catalog, order)?)?;
+
+    Ok(prices)
+}
+
+fn price_bundle(catalog: &Catalog, rest: &str) -> Result<u32, PricingError> {
+    let (name, choices) = rest.split_once('#').unwrap_or((rest, ""));
+    let name = name.trim();
+    let bundle = catalog.bundle(name)
+        .ok_or_else(|| PricingError::UnknownBundle { name: name.to_string() })?;
+
+    let choices: Vec<&str> = choices.split('|')
+        .map(str::trim)
+        .filter(|choice| !choice.is_empty())
+        .collect();
+    if choices.len() != bundle.slots.len() {
+        return Err(PricingError::BundleSlotCount {
+            name: bundle.name.clone(),
+            expected: bundle.slots.len(),
+            actual: choices.len(),
+        });
+    }
+
+    for (slot, choice) in bundle.slots.iter().zip(choices) {
+        if !slot.allows(catalog, choice) {
+            return Err(PricingError::NotAllowedInSlot {
+                bundle: bundle.name.clone(),
+                slot: slot.label.clone(),
+                item: choice.to_string(),
+            });
+        }
+    }
+
+    Ok(bundle.price * 100)
+}
+
+//Mindestanzahl gilt nur für Custom-Pizzen, angepasste Vorlagen dürfen ohne Toppings sein
+fn check_topping_limits(catalog: &Catalog, scope: Scope, names: &[&str]) -> Result<(), PricingError> {
+    let limits = &catalog.limits;
+
+    for name in names {
+        let count = names.iter().filter(|other| *other == name).count() as u32;
+        let max = limits.max_for(name);
+        if count > max {
+            return Err(PricingError::ToppingAboveMaximum { name: name.to_string(), max });
+        }
+    }
+    for limit in &limits.per_topping {
+        let min = limits.min_for(&limit.name);
+        let count = names.iter().filter(|name| **name == limit.name).count() as u32;
+        if count < min {
+            return Err(PricingError::ToppingBelowMinimum { name: limit.name.clone(), min });
+        }
+    }
+
+    let total = names.len() as u32;
+    if total > limits.max_total {
+        return Err(PricingError::TooManyToppings { max: limits.max_total });
+    }
+    if scope == Scope::Custom && total < limits.min_total {
+        return Err(PricingError::TooFewToppings { min: limits.min_total });
+    }
+    Ok(())
+}
+
+//"Ham" liegt auf der ganzen Pizza, "Ham@L" bzw. "Ham@R" nur auf der linken/rechten Hälfte
+pub fn split_placement(token: &str) -> (&str, bool) {
+    match token.rsplit_once('@') {
+        Some((name, "L" | "R")) => (name.trim(), true),
+        _ => (token, false),
+    }
+}
+
+//Toppingliste "Ham|Ham@L|Corn@R": Limits prüfen und Preis in Cent berechnen
+fn price_custom_toppings(catalog: &Catalog, scope: Scope, list: &str) -> Result<u32, PricingError> {
+    let toppings: Vec<(&str, bool)> = list.split('|')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(split_placement)
+        .collect();
+    let names: Vec<&str> = toppings.iter().map(|(name, _)| *name).collect();
+    check_topping_limits(catalog, scope, &names)?;
+    sum_custom_toppings_cents(catalog, &toppings)
+}
+
+fn sum_custom_toppings_cents(catalog: &Catalog, toppings: &[(&str, bool)]) -> Result<u32, PricingError> {
+    let mut sum = 0;
+    for &(name, half) in toppings {
+        let topping = catalog.topping(name)
+            .ok_or_else(|| PricingError::UnknownTopping { name: name.to_string() })?;
+        sum += if half { topping.price * HALF_TOPPING_PERCENT } else { topping.price * 100 };
+    }
+    Ok(sum)
+}
+
+fn sum_toppings<'a>(catalog: &Catalog, names: impl Iterator<Item = &'a str>) -> Result<u32, PricingError> {
+    let mut sum = 0;
+    for name in names {
+        let topping = catalog.topping(name)
+            .ok_or_else(|| PricingError::UnknownTopping { name: name.to_string() })?;
+        sum += topping.price;
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::{parse_bundles, parse_items, parse_topping_limits, DeliverySettings, Prebuilt, Topping};
+    use crate::stock::{parse_stock, Stock};
+
+    fn happy_hour() -> PricingRule {
+        parse_pricing_rules("Happy Hour#Mon-Fri#15:00-17:00#20#prebuilt").unwrap().remove(0)
+    }
+
+    fn catalog() -> Catalog {
+        Catalog {
+            toppings: vec![
+                Topping { name: String::from("Ham"), price: 6 },
+                Topping { name: String::from("Onions"), price: 2 },
+            ],
+            prebuilts: vec![
+                Prebuilt { name: String::from("Prosciutto"), toppings: vec![String::from("Ham")], base_price: 4 },
+                Prebuilt { name: String::from("Margherita"), toppings: vec![], base_price: 4 },
+            ],
+            items: parse_items("drink#Cola#3").unwrap(),
+            bundles: parse_bundles("Duo Deal#Pizza:*;Pizza:Margherita#9\nMenu#Pizza:*;Drink:*drink#10").unwrap(),
+            limits: parse_topping_limits("*#1#3\nHam#0#2").unwrap(),
+            delivery: DeliverySettings { fee: 3, minimum: 10 },
+            stock: Stock::default(),
+        }
+    }
+
+    #[test]
+    fn should_parse_pricing_rule() {
+        let rule = happy_hour();
+
+        assert_eq!(rule, PricingRule {
+            name: String::from("Happy Hour"),
+            days: [true, true, true, true, true, false, false],
+            from_minute: 15 * 60,
+            to_minute: 17 * 60,
+            percent: 20,
+            scope: Scope::Prebuilt,
+        });
+    }
+
+    #[test]
+    fn should_activate_rule_only_inside_time_window() {
+        let rules = vec![happy_hour()];
+
+        assert!(active_rule(&rules, 0, 15 * 60).is_some());
+        assert!(active_rule(&rules, 4, 16 * 60 + 59).is_some());
+        assert!(active_rule(&rules, 4, 17 * 60).is_none());
+        assert!(active_rule(&rules, 5, 16 * 60).is_none());
+    }
+
+    #[test]
+    fn should_handle_rules_across_midnight() {
+        let rules = parse_pricing_rules("Late Night#*#22:00-02:00#10#all").unwrap();
+
+        assert!(active_rule(&rules, 6, 23 * 60).is_some());
+        assert!(active_rule(&rules, 6, 60).is_some());
+        assert!(active_rule(&rules, 6, 12 * 60).is_none());
+    }
+
+    #[test]
+    fn should_count_hours_after_midnight_to_the_previous_day() {
+        let rules = parse_pricing_rules("Friday Night#Fri#22:00-02:00#10#all").unwrap();
+
+        assert!(active_rule(&rules, 4, 23 * 60).is_some());
+        assert!(active_rule(&rules, 5, 30).is_some());
+        assert!(active_rule(&rules, 4, 30).is_none());
+        assert!(active_rule(&rules, 5, 23 * 60).is_none());
+        assert!(active_rule(&rules, 5, 2 * 60).is_none());
+
+        let sunday = parse_pricing_rules("Sunday Night#Sun#22:00-02:00#10#all").unwrap();
+        assert!(active_rule(&sunday, 0, 60).is_some());
+    }
+
+    #[test]
+    fn should_reject_invalid_rules() {
+        assert!(parse_pricing_rules("Broken#Mon#15:00#20#prebuilt").is_err());
+        assert!(parse_pricing_rules("Broken#Someday#15:00-16:00#20#prebuilt").is_err());
+        assert!(parse_pricing_rules("Broken#Mon#15:00-16:00#120#prebuilt").is_err());
+    }
+
+    #[test]
+    fn should_price_order_with_active_rule() {
+        let rule = happy_hour();
+
+        let prices = price_order(&catalog(), Some(&rule), "prebuilt#Prosciutto\ncustom#Ham|Onions").unwrap();
+
+        assert_eq!(prices, vec![800, 1400]);
+    }
+
+    #[test]
+    fn should_price_bundle_without_pricing_rule() {
+        let rule = parse_pricing_rules("Always#*#00:00-24:00#50#all").unwrap().remove(0);
+
+        let prices = price_order(&catalog(), Some(&rule), "bundle#Duo Deal#Prosciutto|Margherita").unwrap();
+
+        assert_eq!(prices, vec![900]);
+    }
+
+    #[test]
+    fn should_price_items_and_bundles_with_items() {
+        let rule = parse_pricing_rules("Drinks#*#00:00-24:00#50#item").unwrap().remove(0);
+
+        let prices = price_order(&catalog(), Some(&rule), "item#Cola\nbundle#Menu#Margherita|Cola\nprebuilt#Margherita").unwrap();
+
+        assert_eq!(prices, vec![150, 1000, 400]);
+    }
+
+    #[test]
+    fn should_reject_bundle_choice_outside_slot() {
+        let error = price_order(&catalog(), None, "bundle#Duo Deal#Prosciutto|Prosciutto").unwrap_err();
+
+        assert!(matches!(error, PricingError::NotAllowedInSlot { item, .. } if item == "Prosciutto"));
+    }
+
+    #[test]
+    fn should_enforce_topping_limits_for_custom_pizza() {
+        let catalog = catalog();
+
+        assert!(price_order(&catalog, None, "custom#Ham|Ham|Onions").is_ok());
+        assert!(matches!(
+            price_order(&catalog, None, "custom#Ham|Ham|Ham").unwrap_err(),
+            PricingError::ToppingAboveMaximum { max: 2, .. }
+        ));
+        assert!(matches!(
+            price_order(&catalog, None, "custom#Onions|Onions|Onions|Ham").unwrap_err(),
+            PricingError::TooManyToppings { max: 3 }
+        ));
+        assert!(matches!(
+            price_order(&catalog, None, "custom#").unwrap_err(),
+            PricingError::TooFewToppings { min: 1 }
+        ));
+    }
+
+    #[test]
+    fn should_price_half_toppings_at_half_price() {
+        let prices = price_order(&catalog(), None, "custom#Ham@L|Onions@R|Onions").unwrap();
+
+        assert_eq!(prices, vec![600 + 300 + 100 + 200]);
+    }
+
+    #[test]
+    fn should_price_customized_prebuilt_from_its_base_price() {
+        let rule = happy_hour();
+
+        let prices = price_order(&catalog(), Some(&rule), "prebuilt#Prosciutto#Onions|Onions@L").unwrap();
+
+        assert_eq!(prices, vec![(400 + 200 + 100) * 80 / 100]);
+    }
+
+    #[test]
+    fn should_not_require_minimum_toppings_on_customized_prebuilt() {
+        let catalog = catalog();
+
+        assert_eq!(price_order(&catalog, None, "prebuilt#Margherita#").unwrap(), vec![400]);
+        assert!(matches!(
+            price_order(&catalog, None, "custom#").unwrap_err(),
+            PricingError::TooFewToppings { min: 1 }
+        ));
+    }
+
+    #[test]
+    fn should_count_half_toppings_against_limits() {
+        assert!(matches!(
+            price_order(&catalog(), None, "custom#Ham@L|Ham@R|Ham").unwrap_err(),
+            PricingError::ToppingAboveMaximum { max: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn should_add_delivery_fee_above_minimum() {
+        let prices = price_order(&catalog(), None, "prebuilt#Prosciutto\nprebuilt#Margherita\ndelivery#").unwrap();
+        assert_eq!(prices, vec![1000, 400, 300]);
+
+        assert!(matches!(
+            price_order(&catalog(), None, "prebuilt#Margherita\ndelivery#").unwrap_err(),
+            PricingError::BelowDeliveryMinimum { minimum: 10 }
+        ));
+        assert!(price_order(&catalog(), None, "prebuilt#Prosciutto\ndelivery#\ndelivery#").is_err());
+    }
+
+    #[test]
+    fn should_reject_unknown_topping() {
+        let error = price_order(&catalog(), None, "custom#Ham|Anchovies").unwrap_err();
+
+        assert!(matches!(error, PricingError::UnknownTopping { name } if name == "Anchovies"));
+    }
+
+    #[test]
+    fn should_reject_prebuilt_with_sold_out_topping() {
+        let catalog = Catalog { stock: parse_stock("Ham#1\nOnions#0").unwrap(), ..catalog() };
+
+        assert!(price_order(&catalog, None, "prebuilt#Prosciutto\nprebuilt#Margherita").is_ok());
+        assert!(matches!(
+            price_order(&catalog, None, "prebuilt#Prosciutto\ncustom#Ham@L").unwrap_err(),
+            PricingError::OutOfStock { available: 1, .. }
+        ));
+        assert_eq!(
+            price_order(&catalog, None, "custom#Onions").unwrap_err().to_string(),
+            "Onions ist ausverkauft"
+        );
+    }
+}
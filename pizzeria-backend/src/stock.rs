@@ -0,0 +1,234 @@
+//Lagerbestand je Topping; Toppings ohne Eintrag werden nicht gezählt (unbegrenzt)
+use crate::catalog::Catalog;
+use crate::custom_error::PricingError;
+use crate::pricing::split_placement;
+
+pub const STOCK_FILE: &str = "stock_text";
+//Kennungen bereits gebuchter Bestellungen, eine je Zeile
+pub const BOOKINGS_FILE: &str = "stock_bookings_text";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct StockEntry {
+    pub name: String,
+    pub count: u32,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Stock {
+    pub entries: Vec<StockEntry>,
+}
+
+impl Stock {
+
+    //None = Bestand wird nicht geführt
+    pub fn count(&self, name: &str) -> Option<u32> {
+        self.entries.iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.count)
+    }
+
+    //prüft den Verbrauch einer Bestellung gegen den Bestand
+    pub fn check(&self, usage: &[(String, u32)]) -> Result<(), PricingError> {
+        for (name, needed) in usage {
+            if let Some(available) = self.count(name)
+                && available < *needed {
+                    return Err(PricingError::OutOfStock { name: name.clone(), available });
+            }
+        }
+        Ok(())
+    }
+
+    //zieht den Verbrauch ab; bei Fehlbestand bleibt der Bestand unverändert
+    pub fn consume(&mut self, usage: &[(String, u32)]) -> Result<(), PricingError> {
+        self.check(usage)?;
+        for (name, needed) in usage {
+            if let Some(entry) = self.entries.iter_mut().find(|entry| entry.name == *name) {
+                entry.count -= needed;
+            }
+        }
+        Ok(())
+    }
+
+    //nicht geführte Toppings werden mit der Menge neu aufgenommen; bei Überlauf bleibt der Bestand unverändert
+    pub fn restock(&mut self, name: &str, amount: u32) -> Result<(), String> {
+        match self.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => {
+                entry.count = entry.count.checked_add(amount)
+                    .ok_or_else(|| format!("Bestand für '{name}' zu groß"))?;
+            }
+            None => self.entries.push(StockEntry { name: name.to_string(), count: amount }),
+        }
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+        self.entries.iter()
+            .map(|entry| format!("{}#{}\n", entry.name, entry.count))
+            .collect()
+    }
+}
+
+//erste Zeile "booking#<Kennung>" macht eine Buchung wiederholbar; ohne Kennung wird immer gebucht
+pub fn split_booking(body: &str) -> (Option<&str>, &str) {
+    let (first, rest) = body.split_once('\n').unwrap_or((body, ""));
+    match first.trim().strip_prefix("booking#").map(str::trim) {
+        Some(key) if !key.is_empty() => (Some(key), rest),
+        _ => (None, body),
+    }
+}
+
+pub fn is_booked(bookings: &str, key: &str) -> bool {
+    bookings.lines().any(|line| line.trim() == key)
+}
+
+fn parse_name_and_count(line: &str, lineno: usize) -> Result<(String, u32), String> {
+    let (name, count_text) = line.split_once('#')
+        .ok_or_else(|| format!("Zeile {}: erwartet 'Topping#Menge'", lineno + 1))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("Zeile {}: Topping-Name fehlt", lineno + 1));
+    }
+    let count = count_text.trim().parse::<u32>()
+        .map_err(|_| format!("Zeile {}: Ungültige Menge '{}'", lineno + 1, count_text.trim()))?;
+    Ok((name.to_string(), count))
+}
+
+// Format: <Topping>#<Bestand>
+pub fn parse_stock(content: &str) -> Result<Stock, String> {
+    let mut stock = Stock::default();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (name, count) = parse_name_and_count(line, lineno)?;
+        stock.restock(&name, count)
+            .map_err(|e| format!("Zeile {}: {}", lineno + 1, e))?;
+    }
+
+    Ok(stock)
+}
+
+// Format: <Topping>#<Zugang> je Zeile
+pub fn parse_restock(body: &str) -> Result<Vec<(String, u32)>, String> {
+    body.lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(lineno, line)| parse_name_and_count(line, lineno))
+        .collect()
+}
+
+fn add_usage(usage: &mut Vec<(String, u32)>, name: &str) {
+    match usage.iter_mut().find(|(used, _)| used == name) {
+        Some((_, count)) => *count += 1,
+        None => usage.push((name.to_string(), 1)),
+    }
+}
+
+//Toppingliste "Ham|Ham@L|Corn@R": jede Portion zählt einfach, auch auf einer Hälfte
+fn add_topping_list(usage: &mut Vec<(String, u32)>, list: &str) {
+    for token in list.split('|').map(str::trim).filter(|token| !token.is_empty()) {
+        add_usage(usage, split_placement(token).0);
+    }
+}
+
+//Toppingverbrauch einer Bestellung (Bestellzeilen wie bei POST /order/price)
+pub fn topping_usage(catalog: &Catalog, order: &str) -> Result<Vec<(String, u32)>, PricingError> {
+    let mut usage = Vec::new();
+
+    for line in order.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (kind, rest) = line.split_once('#')
+            .ok_or_else(|| PricingError::InvalidLine { line: line.to_string() })?;
+
+        match kind {
+            "prebuilt" => match rest.split_once('#') {
+                Some((_, toppings)) => add_topping_list(&mut usage, toppings),
+                None => {
+                    let prebuilt = catalog.prebuilt(rest.trim())
+                        .ok_or_else(|| PricingError::UnknownPizza { name: rest.trim().to_string() })?;
+                    for topping in &prebuilt.toppings {
+                        add_usage(&mut usage, topping);
+                    }
+                }
+            },
+            "custom" => add_topping_list(&mut usage, rest),
+            //Bundle-Auswahl: nur Prebuilt-Pizzen verbrauchen Toppings
+            "bundle" => {
+                let choices = rest.split_once('#').map(|(_, choices)| choices).unwrap_or("");
+                for choice in choices.split('|').map(str::trim) {
+                    if let Some(prebuilt) = catalog.prebuilt(choice) {
+                        for topping in &prebuilt.toppings {
+                            add_usage(&mut usage, topping);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::{parse_bundles, parse_items, parse_prebuilts, parse_toppings};
+
+    fn catalog() -> Catalog {
+        Catalog {
+            toppings: parse_toppings("Ham#6\nMushrooms#7\nCorn#4").unwrap(),
+            prebuilts: parse_prebuilts("Funghi#Mushrooms#4\nHawaii#Ham|Corn#4").unwrap(),
+            items: parse_items("drink#Cola#3").unwrap(),
+            bundles: parse_bundles("Duo Deal#Pizza:*;Drink:*drink#9").unwrap(),
+            stock: parse_stock("Mushrooms#2\nHam#5").unwrap(),
+            ..Catalog::default()
+        }
+    }
+
+    #[test]
+    fn test_topping_usage_counts_all_order_lines() {
+        let order = "prebuilt#Funghi\nprebuilt#Hawaii#Ham|Ham@L\ncustom#Corn|Mushrooms@R\nbundle#Duo Deal#Funghi|Cola\nitem#Cola";
+        let usage = topping_usage(&catalog(), order).unwrap();
+
+        assert_eq!(usage, vec![
+            (String::from("Mushrooms"), 3),
+            (String::from("Ham"), 2),
+            (String::from("Corn"), 1),
+        ]);
+    }
+
+    #[test]
+    fn test_consume_and_restock() {
+        let mut stock = catalog().stock;
+
+        assert!(stock.consume(&[(String::from("Mushrooms"), 2), (String::from("Corn"), 9)]).is_ok());
+        assert_eq!(stock.count("Mushrooms"), Some(0));
+        assert_eq!(stock.count("Corn"), None);
+
+        let error = stock.consume(&[(String::from("Ham"), 1), (String::from("Mushrooms"), 1)]).unwrap_err();
+        assert!(matches!(error, PricingError::OutOfStock { available: 0, .. }));
+        assert_eq!(stock.count("Ham"), Some(5));
+
+        for (name, amount) in parse_restock("Mushrooms#10\nCorn#3\n").unwrap() {
+            stock.restock(&name, amount).unwrap();
+        }
+        assert_eq!(stock.to_text(), "Mushrooms#10\nHam#5\nCorn#3\n");
+        assert!(parse_restock("Corn#viel").is_err());
+
+        assert_eq!(stock.restock("Ham", u32::MAX), Err(String::from("Bestand für 'Ham' zu groß")));
+        assert_eq!(stock.count("Ham"), Some(5));
+    }
+
+    #[test]
+    fn test_split_booking_key() {
+        assert_eq!(split_booking("booking#4711-1\nprebuilt#Funghi\nitem#Cola"), (Some("4711-1"), "prebuilt#Funghi\nitem#Cola"));
+        assert_eq!(split_booking("prebuilt#Funghi"), (None, "prebuilt#Funghi"));
+        assert_eq!(split_booking("booking#\nprebuilt#Funghi"), (None, "booking#\nprebuilt#Funghi"));
+
+        assert!(is_booked("4711-0\n4711-1\n", "4711-1"));
+        assert!(!is_booked("4711-10\n", "4711-1"));
+    }
+}
@@ -0,0 +1,196 @@
+//Warenkorb: sammelt alle Positionen einer Bestellung bis zum Checkout
+
+use crate::transactions::join_transaction_items;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CartEntry {
+    pub name: String,
+    pub order_line: String,     //Bestellzeile für POST /order/price
+    pub unit_cents: u32,
+    pub quantity: u32,
+    pub note: Option<String>,       //z.B. "well done"
+    pub details: Vec<String>,       //Toppings bzw. Bundle-Auswahl für den Bon
+}
+
+impl CartEntry {
+
+    pub fn new(name: String, order_line: String, unit_cents: u32) -> Self {
+        CartEntry { name, order_line, unit_cents, quantity: 1, note: None, details: Vec::new() }
+    }
+
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn total_cents(&self) -> u32 {
+        self.unit_cents * self.quantity
+    }
+
+    pub fn display_name(&self) -> String {
+        let name = if self.quantity > 1 {
+            format!("{}x {}", self.quantity, self.name)
+        } else {
+            self.name.clone()
+        };
+        match &self.note {
+            Some(note) => format!("{name} [{note}]"),
+            None => name,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Cart {
+    pub entries: Vec<CartEntry>,
+    pub note: Option<String>,       //Notiz zur gesamten Bestellung
+}
+
+//leere Eingabe entfernt die Notiz
+pub fn note_from_input(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+impl Cart {
+
+    //gleiche Positionen (inkl. Notiz) werden zusammengefasst
+    pub fn add(&mut self, entry: CartEntry) {
+        match self.entries.iter_mut().find(|e| e.order_line == entry.order_line && e.name == entry.name && e.note == entry.note) {
+            Some(existing) => {
+                existing.quantity += entry.quantity;
+                existing.unit_cents = entry.unit_cents;
+            }
+            None => self.entries.push(entry),
+        }
+    }
+
+    pub fn increment(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.quantity += 1;
+        }
+    }
+
+    //bei Menge 0 wird die Position entfernt
+    pub fn decrement(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.quantity -= 1;
+            if entry.quantity == 0 {
+                self.entries.remove(index);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+    }
+
+    pub fn set_note(&mut self, index: usize, note: Option<String>) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.note = note;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.note = None;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn item_count(&self) -> u32 {
+        self.entries.iter().map(|e| e.quantity).sum()
+    }
+
+    pub fn total_cents(&self) -> u32 {
+        self.entries.iter().map(CartEntry::total_cents).sum()
+    }
+
+    //eine Bestellzeile je Stück, damit das Backend den Warenwert (z.B. Mindestbestellwert) kennt
+    pub fn order_text(&self) -> String {
+        self.entries.iter()
+            .flat_map(|e| std::iter::repeat_n(e.order_line.as_str(), e.quantity as usize))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    //Stückpreise passend zu order_text()
+    pub fn unit_prices(&self) -> Vec<u32> {
+        self.entries.iter()
+            .flat_map(|e| std::iter::repeat_n(e.unit_cents, e.quantity as usize))
+            .collect()
+    }
+
+    pub fn apply_prices(&mut self, prices: &[u32]) {
+        let mut offset = 0;
+        for entry in self.entries.iter_mut() {
+            if let Some(&cents) = prices.get(offset) {
+                entry.unit_cents = cents;
+            }
+            offset += entry.quantity as usize;
+        }
+    }
+
+    //Name der gemeinsamen Transaktion, z.B. "2x Hawaii, Cola"
+    pub fn transaction_name(&self) -> String {
+        let names: Vec<String> = self.entries.iter().map(CartEntry::display_name).collect();
+        join_transaction_items(names.iter().map(String::as_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, order_line: &str, unit_cents: u32) -> CartEntry {
+        CartEntry::new(String::from(name), String::from(order_line), unit_cents)
+    }
+
+    #[test]
+    fn test_cart_merges_equal_entries() {
+        let mut cart = Cart::default();
+
+        cart.add(entry("Hawaii", "prebuilt#Hawaii", 1500));
+        cart.add(entry("Cola", "item#Cola", 300));
+        cart.add(entry("Hawaii", "prebuilt#Hawaii", 1500));
+
+        assert_eq!(cart.entries.len(), 2);
+        assert_eq!(cart.item_count(), 3);
+        assert_eq!(cart.total_cents(), 3300);
+        assert_eq!(cart.order_text(), "prebuilt#Hawaii\nprebuilt#Hawaii\nitem#Cola");
+        assert_eq!(cart.unit_prices(), vec![1500, 1500, 300]);
+        assert_eq!(cart.transaction_name(), "2x Hawaii, Cola");
+
+        cart.apply_prices(&[1200, 1200, 300]);
+        assert_eq!(cart.total_cents(), 2700);
+    }
+
+    #[test]
+    fn test_cart_notes() {
+        let mut cart = Cart::default();
+        cart.add(entry("Hawaii", "prebuilt#Hawaii", 1500));
+        cart.set_note(0, note_from_input(" well done "));
+        cart.add(entry("Hawaii", "prebuilt#Hawaii", 1500));
+
+        assert_eq!(cart.entries.len(), 2);
+        assert_eq!(cart.transaction_name(), "Hawaii [well done], Hawaii");
+        assert_eq!(note_from_input("  "), None);
+    }
+
+    #[test]
+    fn test_cart_decrement_removes_entry() {
+        let mut cart = Cart::default();
+        cart.add(entry("Cola", "item#Cola", 300));
+        cart.increment(0);
+
+        cart.decrement(0);
+        assert_eq!(cart.entries[0].quantity, 1);
+
+        cart.decrement(0);
+        assert!(cart.is_empty());
+    }
+}
@@ -5,9 +5,13 @@ use std::net::TcpStream;
 use crate::{clear_screen, Arguments};
 use crate::table::{Align, Table, TableCell, TableRow};
 use crate::table_menu::TableMenu;
-use crate::http::{read_toppings};
+use crate::http::{get, read_toppings, send_stock_change};
 use crate::http::request::RequestBuilder;
+use crate::types::{parse_stock, FoodInfo};
 use crate::ui::{wait_enter, prompt};
+use crate::input::{read_input, InputEvent, MouseButton, MouseKind};
+use crate::render::render_menu;
+use crate::style::theme;
 
 // Entfernen nach Nummer oder Name
 pub fn remove_topping(stdout: &mut Stdout, stdin: &mut Stdin, _path: &str, arguments: &Arguments) -> io::Result<()> {
@@ -70,7 +74,7 @@ pub fn remove_topping(stdout: &mut Stdout, stdin: &mut Stdin, _path: &str, argum
         let body_after = read_toppings(arguments)?;
         list_toppings_from_str(stdout, &body_after)?;
 
-        writeln!(stdout, "\x1b[1;31mEntfernt:\x1b[0m \x1b[1m{name}\x1b[0m", name = name_to_delete)?;
+        writeln!(stdout, "{} {}", theme().error.paint("Entfernt:"), theme().emphasis.paint(&name_to_delete))?;
         stdout.flush()?;
 
     Ok(())
@@ -80,11 +84,11 @@ pub fn add_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argument
 
     loop {
         clear_screen(stdout)?;
-        writeln!(stdout, "\x1b[1;31mTopping hinzufügen\x1b[0m (Name, dann Preis). 'q' zum Abbrechen.")?;
+        writeln!(stdout, "{} (Name, dann Preis). 'q' zum Abbrechen.", theme().heading.paint("Topping hinzufügen"))?;
         stdout.flush()?;
 
         let topping_name = {
-            let input = prompt(stdin, stdout, "\x1b[4;34mName\x1b[0m: ")?;
+            let input = prompt(stdin, stdout, &format!("{}: ", theme().prompt.paint("Name")))?;
             let name = input.trim();
             if name.is_empty() || name.eq_ignore_ascii_case("q") {
                 writeln!(stdout, "Abgebrochen.")?;
@@ -94,7 +98,7 @@ pub fn add_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argument
         };
 
         let topping_price: u32 = loop {
-            let input = prompt(stdin, stdout, "\x1b[4;34mPreis (Ganzzahl)\x1b[0m: ")?;
+            let input = prompt(stdin, stdout, &format!("{}: ", theme().prompt.paint("Preis (Ganzzahl)")))?;
             let input = input.trim();
 
             if input.eq_ignore_ascii_case("q") || input.is_empty() {
@@ -111,12 +115,29 @@ pub fn add_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argument
             }
         };
 
-        let line = format!("{}#{}", topping_name, topping_price);
+        // Ernährung/Allergene, z.B. "vegetarian|lactose"
+        let tags = loop {
+            let input = prompt(stdin, stdout, &format!("{} (vegan, vegetarian, gluten, lactose, nuts, egg, fish, soy, celery, mustard; mit '|' trennen, leer = keine): ", theme().prompt.paint("Kennzeichnung")))?;
+            if input.trim().eq_ignore_ascii_case("q") {
+                writeln!(stdout, "Abgebrochen.")?;
+                return Ok(());
+            }
+            match FoodInfo::parse(&input) {
+                Ok(info) => break info.to_tags(),
+                Err(e) => writeln!(stdout, "{e}.")?,
+            }
+        };
+
+        let line = if tags.is_empty() {
+            format!("{}#{}", topping_name, topping_price)
+        } else {
+            format!("{}#{}#{}", topping_name, topping_price, tags)
+        };
         // if !line.ends_with('\n') { line.push('\n'); }
 
         send_post("/toppings", &line, arguments)?;
 
-        writeln!(stdout, "\nErfolgreich hinzugefügt: \x1b[1;32m{} {}\x1b[0m", topping_name, topping_price)?;
+        writeln!(stdout, "\nErfolgreich hinzugefügt: {}", theme().success.paint(&format!("{topping_name} {topping_price}")))?;
         stdout.flush()?;
 
         let again = prompt(stdin, stdout, "Weiteres Topping hinzufügen? (j/n): ")?;
@@ -128,6 +149,88 @@ pub fn add_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argument
     Ok(())
 }
 
+// Nachlieferung nach Nummer oder Name, Menge wird zum Bestand addiert
+pub fn restock_topping(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> Result<(), Box<dyn Error>> {
+
+    let toppings = read_toppings(arguments)?;
+    let stock = get("/stock", arguments)?;
+    list_stock_from_str(stdout, &toppings, &stock)?;
+
+    let names: Vec<&str> = toppings
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    let choice = prompt(stdin, stdout, "\nNachliefern (Nummer oder Name, 'q' zum Abbrechen): ")?;
+    let choice = choice.trim();
+    if choice.eq_ignore_ascii_case("q") || choice.is_empty() {
+        writeln!(stdout, "Abgebrochen.")?;
+        return Ok(());
+    }
+
+    let name = match choice.parse::<usize>() {
+        Ok(idx1) => names.get(idx1.wrapping_sub(1)).copied(),
+        Err(_) => names.iter().copied().find(|name| name.eq_ignore_ascii_case(choice)),
+    };
+    let Some(name) = name else {
+        writeln!(stdout, "Kein passendes Topping gefunden.")?;
+        return Ok(());
+    };
+
+    let amount: u32 = loop {
+        let input = prompt(stdin, stdout, &format!("{}: ", theme().prompt.paint("Menge (Ganzzahl)")))?;
+        let input = input.trim();
+
+        if input.eq_ignore_ascii_case("q") || input.is_empty() {
+            writeln!(stdout, "Abgebrochen.")?;
+            return Ok(());
+        }
+
+        match input.parse::<u32>() {
+            Ok(n) if n > 0 => break n,
+            _ => writeln!(stdout, "Ungültige Menge. Bitte positive Ganzzahl angeben.")?,
+        }
+    };
+
+    let stock_after = send_stock_change("/stock/restock", &format!("{name}#{amount}"), arguments)?;
+    clear_screen(stdout)?;
+    list_stock_from_str(stdout, &toppings, &stock_after)?;
+
+    writeln!(stdout, "{} {}", theme().success.paint("Nachgeliefert:"), theme().emphasis.paint(&format!("{amount}x {name}")))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn list_stock_from_str(stdout: &mut Stdout, toppings: &str, stock: &str) -> io::Result<()> {
+    let mut table = Table::new(vec![]);
+    let counts = parse_stock(stock).unwrap_or_default();
+
+    for (index, name) in toppings.lines().map(|l| l.split('#').next().unwrap_or("").trim()).filter(|name| !name.is_empty()).enumerate() {
+        // ohne Eintrag wird der Bestand nicht gezählt
+        let count = match counts.iter().find(|(stocked, _)| stocked == name) {
+            Some((_, 0)) => String::from("  sold out"),
+            Some((_, count)) => format!("  {count}"),
+            None => String::from("  -"),
+        };
+
+        table.push(TableRow::new(vec![
+            TableCell::new(format!("{}.", index + 1)),
+            TableCell::new(name.to_string()),
+            TableCell::new_with_alignment(count, Align::Right),
+        ]));
+    }
+
+    if table.is_empty() {
+        table.push(TableRow::new(vec![TableCell::new(String::from("Noch keine Toppings vorhanden!"))]));
+    }
+
+    let table_menu = TableMenu::new(String::from("Lagerbestand"), table);
+    writeln!(stdout, "{table_menu}")?;
+    stdout.flush()?;
+    Ok(())
+}
+
 fn send_post(path: &str, body: &str, arguments: &Arguments) -> io::Result<()> {
     let mut stream =  TcpStream::connect(arguments.server_address)?;
     let body_length = body.len();
@@ -191,8 +294,8 @@ pub fn send_clear_toppings(path: &str, arguments: &Arguments) -> io::Result<()>
     ensure_success_code(code)
 }
 
-fn list_toppings_from_str(stdout: &mut Stdout, content: &str) -> io::Result<()> {
-    let title_text = String::from("Aktuelle Toppings");
+//mit Auswahl: zusätzliche Marker-Spalte vorne (siehe browse_toppings)
+fn toppings_table(content: &str, selected: Option<usize>) -> Table {
     let mut table = Table::new(vec![]);
 
     for (index, line) in content.lines().enumerate() {
@@ -202,14 +305,28 @@ fn list_toppings_from_str(stdout: &mut Stdout, content: &str) -> io::Result<()>
         let mut parts = line.split('#');
         let name  = parts.next().unwrap_or("").trim();
         let price = parts.next().unwrap_or("").trim();
+        let tags = parts.next().unwrap_or("").trim();
 
-        table.push(TableRow::new(vec![
+        let mut cells = vec![
             TableCell::new(format!("{}.", index + 1)),
             TableCell::new(name.to_string()),
             TableCell::new_with_alignment(format!("{}.00$", price), Align::Right),
-        ]));
+            TableCell::new(format!(" {tags}")),
+        ];
+        if let Some(selected) = selected {
+            let marker = if table.len() == selected { ">" } else { " " };
+            cells.insert(0, TableCell::new(marker.into()));
+        }
+        table.push(TableRow::new(cells));
     }
 
+    table
+}
+
+fn list_toppings_from_str(stdout: &mut Stdout, content: &str) -> io::Result<()> {
+    let title_text = String::from("Aktuelle Toppings");
+    let table = toppings_table(content, None);
+
     if table.is_empty() {
         let table = Table::new(vec![
             TableRow::new(vec![TableCell::new(String::from("Noch keine Toppings vorhanden!"))]),
@@ -226,6 +343,46 @@ fn list_toppings_from_str(stdout: &mut Stdout, content: &str) -> io::Result<()>
     Ok(())
 }
 
+//Topping-Liste zum Durchblättern, auch wenn sie nicht auf eine Bildschirmseite passt
+pub fn browse_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> Result<(), Box<dyn Error>> {
+    let body = read_toppings(arguments)?;
+    if toppings_table(&body, None).is_empty() {
+        list_toppings_from_str(stdout, &body)?;
+        wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+        return Ok(());
+    }
+
+    let mut selected_row: usize = 0;
+    let mut offset: usize = 0;
+
+    loop {
+        let table = toppings_table(&body, Some(selected_row));
+        let length = table.len();
+        let mut tm = TableMenu::new(String::from("Aktuelle Toppings"), table);
+        tm.set_offset(offset);
+
+        let footer = ["", "[↑/↓] bewegen · [Bild↑/Bild↓] Seite · [Enter/Backspace] zurück"];
+        render_menu(stdout, &mut tm, "ToppingList", selected_row, &footer)?;
+        stdout.flush()?;
+        offset = tm.offset();
+
+        match read_input(stdin)? {
+            InputEvent::Up => selected_row = if selected_row > 0 { selected_row - 1 } else { length - 1 },
+            InputEvent::Down => selected_row = if selected_row + 1 < length { selected_row + 1 } else { 0 },
+            InputEvent::PageUp => selected_row = selected_row.saturating_sub(tm.page_size()),
+            InputEvent::PageDown => selected_row = (selected_row + tm.page_size()).min(length - 1),
+            InputEvent::Mouse(mouse) => match mouse.kind {
+                MouseKind::WheelUp => selected_row = selected_row.saturating_sub(1),
+                MouseKind::WheelDown => selected_row = (selected_row + 1).min(length - 1),
+                MouseKind::Press(MouseButton::Left) => selected_row = tm.row_at(mouse.column, mouse.row).unwrap_or(selected_row),
+                _ => {}
+            },
+            InputEvent::Enter | InputEvent::Back | InputEvent::Exit => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
 pub fn list_toppings_from_backend(stdout: &mut Stdout, arguments: &Arguments) -> io::Result<()> {
     let body = read_toppings(arguments)?;
     list_toppings_from_str(stdout, &body)
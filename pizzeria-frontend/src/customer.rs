@@ -0,0 +1,160 @@
+use std::io;
+use std::io::{Stdin, Stdout, Write};
+use crate::pricing::format_price;
+use crate::ui::prompt;
+pub use pizzeria_shared::delivery::{parse_delivery_settings, DeliverySettings};
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OrderType {
+    #[default]
+    Pickup,
+    Delivery,
+    EatIn,
+}
+
+impl OrderType {
+    pub const ALL: [OrderType; 3] = [OrderType::Pickup, OrderType::Delivery, OrderType::EatIn];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderType::Pickup => "Pickup",
+            OrderType::Delivery => "Delivery",
+            OrderType::EatIn => "Eat-in",
+        }
+    }
+
+    //Kennung in der Transaktionszeile
+    pub fn code(&self) -> &'static str {
+        match self {
+            OrderType::Pickup => "pickup",
+            OrderType::Delivery => "delivery",
+            OrderType::EatIn => "eat-in",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CustomerDetails {
+    pub order_type: OrderType,
+    pub name: String,
+    pub phone: String,
+    pub address: Option<String>,
+}
+
+pub fn parse_order_type(input: &str) -> Option<OrderType> {
+    match input.trim().to_lowercase().as_str() {
+        "" | "1" | "p" | "pickup" => Some(OrderType::Pickup),
+        "2" | "d" | "delivery" => Some(OrderType::Delivery),
+        "3" | "e" | "eat-in" => Some(OrderType::EatIn),
+        _ => None,
+    }
+}
+
+//Ziffern, Leerzeichen und + - / ( ), mindestens 5 Ziffern
+pub fn is_valid_phone(phone: &str) -> bool {
+    let digits = phone.chars().filter(char::is_ascii_digit).count();
+    digits >= 5 && phone.chars().all(|c| c.is_ascii_digit() || " +-/()".contains(c))
+}
+
+fn is_cancel(input: &str) -> bool {
+    input.eq_ignore_ascii_case("q")
+}
+
+// Pflichtfeld: leere Eingabe wiederholt die Abfrage, 'q' bricht ab
+fn prompt_required(stdin: &mut Stdin, stdout: &mut Stdout, label: &str) -> io::Result<Option<String>> {
+    loop {
+        let input = prompt(stdin, stdout, label)?;
+        if is_cancel(&input) {
+            return Ok(None);
+        }
+        if !input.is_empty() {
+            return Ok(Some(input));
+        }
+        writeln!(stdout, "Pflichtfeld - bitte ausfüllen.")?;
+    }
+}
+
+//Kundendaten für den Checkout abfragen; None = abgebrochen
+pub fn customer_form(stdin: &mut Stdin, stdout: &mut Stdout, delivery: &DeliverySettings) -> io::Result<Option<CustomerDetails>> {
+    writeln!(stdout, "Bestellart ('q' zum Abbrechen):")?;
+    for (i, order_type) in OrderType::ALL.iter().enumerate() {
+        writeln!(stdout, "  {}: {}", i + 1, order_type.label())?;
+    }
+    writeln!(stdout, "Lieferung: {} Gebühr, Mindestbestellwert {}",
+             format_price(delivery.fee * 100), format_price(delivery.minimum * 100))?;
+
+    let order_type = loop {
+        let input = prompt(stdin, stdout, "Auswahl [1]: ")?;
+        if is_cancel(&input) {
+            return Ok(None);
+        }
+        match parse_order_type(&input) {
+            Some(order_type) => break order_type,
+            None => writeln!(stdout, "Ungültige Bestellart '{input}'.")?,
+        }
+    };
+
+    //vor Ort ist der Name optional
+    let name = if order_type == OrderType::EatIn {
+        let input = prompt(stdin, stdout, "Name (optional): ")?;
+        if is_cancel(&input) {
+            return Ok(None);
+        }
+        input
+    } else {
+        match prompt_required(stdin, stdout, "Name: ")? {
+            Some(name) => name,
+            None => return Ok(None),
+        }
+    };
+
+    //Telefon ist nur bei Lieferung Pflicht
+    let phone = loop {
+        let input = if order_type == OrderType::Delivery {
+            match prompt_required(stdin, stdout, "Telefon: ")? {
+                Some(phone) => phone,
+                None => return Ok(None),
+            }
+        } else {
+            prompt(stdin, stdout, "Telefon (optional): ")?
+        };
+        if is_cancel(&input) {
+            return Ok(None);
+        }
+        if input.is_empty() || is_valid_phone(&input) {
+            break input;
+        }
+        writeln!(stdout, "Ungültige Telefonnummer '{input}'.")?;
+    };
+
+    let address = if order_type == OrderType::Delivery {
+        match prompt_required(stdin, stdout, "Adresse: ")? {
+            Some(address) => Some(address),
+            None => return Ok(None),
+        }
+    } else {
+        None
+    };
+
+    Ok(Some(CustomerDetails { order_type, name, phone, address }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_order_type() {
+        assert_eq!(parse_order_type(""), Some(OrderType::Pickup));
+        assert_eq!(parse_order_type("2"), Some(OrderType::Delivery));
+        assert_eq!(parse_order_type("Eat-in"), Some(OrderType::EatIn));
+        assert_eq!(parse_order_type("4"), None);
+    }
+
+    #[test]
+    fn test_phone_validation() {
+        assert!(is_valid_phone("+49 (0)30 123-456"));
+        assert!(!is_valid_phone("call me"));
+        assert!(!is_valid_phone("123"));
+    }
+}
@@ -30,6 +30,9 @@ pub enum FrontendError {
     HttpStatus {
         code: u16,
     },
+    Rejected {
+        message: String,
+    },
     InvalidContentLength {
         value: String,
         source: ParseIntError
@@ -38,6 +41,10 @@ pub enum FrontendError {
         source: FromUtf8Error
     },
     UnexpectedEof,
+    Theme {
+        path: String,
+        message: String,
+    },
 }
 
 impl Display for FrontendError {
@@ -55,12 +62,16 @@ impl Display for FrontendError {
                 write!(f, "Nicht gültiges Unicode."),
             FrontendError::HttpStatus { code} =>
                 write!(f, "Backend antwortet mit HTTP {code}."),
+            FrontendError::Rejected { message } =>
+                write!(f, "{message}."),
             FrontendError::InvalidContentLength { value, ..} =>
                 write!(f, "Ungültige Länge '{value}'."),
             FrontendError::BodyUtf8 { .. } =>
                 write!(f, "Nicht gültiges UTF8."),
             FrontendError::UnexpectedEof =>
-                write!(f, "Unerwartes Ende der Verbindung - Antwort unvollständig.")
+                write!(f, "Unerwartetes Ende der Verbindung - Antwort unvollständig."),
+            FrontendError::Theme { path, message } =>
+                write!(f, "Theme '{path}': {message}"),
         }
     }
 }
@@ -74,9 +85,11 @@ impl Error for FrontendError {
             FrontendError::NotUnicode { source, .. } => Some(source),
             FrontendError::NotUnicodeArg => None,
             FrontendError::HttpStatus { .. } => None,
+            FrontendError::Rejected { .. } => None,
             FrontendError::InvalidContentLength { source , .. } => Some(source),
             FrontendError::BodyUtf8 { source, .. } => Some(source),
             FrontendError::UnexpectedEof => None,
+            FrontendError::Theme { .. } => None,
         }
     }
 }
@@ -90,9 +103,11 @@ impl FrontendError {
             FrontendError::NotUnicode { .. } => InvalidInput,
             FrontendError::NotUnicodeArg  => InvalidInput,
             FrontendError::HttpStatus { .. } => Other,
+            FrontendError::Rejected { .. } => InvalidInput,
             FrontendError::InvalidContentLength { .. } => InvalidData,
             FrontendError::BodyUtf8 { .. } => InvalidData,
             FrontendError::UnexpectedEof  => InvalidData,
+            FrontendError::Theme { .. } => InvalidData,
         };
         io::Error::new(kind, self)
     }
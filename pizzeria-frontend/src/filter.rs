@@ -0,0 +1,109 @@
+//Suche in Menüs: '/' startet den Filter, weitere Zeichen grenzen die Zeilen ein,
+//Backspace löscht das letzte Zeichen, Esc beendet die Suche.
+//Ohne '/' sind einzelne Buchstaben Hotkeys bzw. Befehle, daher kein Filtern beim bloßen Tippen
+use crate::input::InputEvent;
+
+//unscharfe Suche: alle Zeichen der Eingabe kommen in dieser Reihenfolge im Text vor ("xch" -> "Xtra Cheese")
+pub fn fuzzy_match(query: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    query.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| text.any(|t| t == q))
+}
+
+//true, wenn die Eingabe den Filter geändert hat
+pub fn edit_filter(filter: &mut Option<String>, input: &InputEvent) -> bool {
+    match (filter.as_mut(), input) {
+        (None, InputEvent::Char('/')) => {
+            *filter = Some(String::new());
+            true
+        }
+        (Some(query), InputEvent::Char(c)) => {
+            query.push(*c);
+            true
+        }
+        (Some(query), InputEvent::Back) => {
+            if query.pop().is_none() {
+                *filter = None;
+            }
+            true
+        }
+        (Some(_), InputEvent::Escape) => {
+            *filter = None;
+            true
+        }
+        _ => false,
+    }
+}
+
+//Auswahl auf eine sichtbare Zeile ziehen, falls die bisherige herausgefiltert wurde
+pub fn snap_row(rows: &[usize], current: usize) -> usize {
+    match rows.first() {
+        Some(first) if !rows.contains(&current) => *first,
+        _ => current,
+    }
+}
+
+//↑/↓ über die sichtbaren Zeilen, mit Umbruch
+pub fn step_row(rows: &[usize], current: usize, down: bool) -> usize {
+    if rows.is_empty() {
+        return current;
+    }
+    let Some(position) = rows.iter().position(|row| *row == current) else {
+        return rows[0];
+    };
+    let next = if down {
+        (position + 1) % rows.len()
+    } else {
+        (position + rows.len() - 1) % rows.len()
+    };
+    rows[next]
+}
+
+//PageUp/PageDown über die sichtbaren Zeilen, ohne Umbruch
+pub fn page_row(rows: &[usize], current: usize, page: usize, down: bool) -> usize {
+    if rows.is_empty() {
+        return current;
+    }
+    let position = rows.iter().position(|row| *row == current).unwrap_or(0);
+    let next = if down {
+        (position + page).min(rows.len() - 1)
+    } else {
+        position.saturating_sub(page)
+    };
+    rows[next]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("xtra", "T: Xtra Cheese 2.00$"));
+        assert!(fuzzy_match("xch", "Xtra Cheese"));
+        assert!(fuzzy_match("käse", "Käse"));
+        assert!(fuzzy_match("", "Ham"));
+        assert!(!fuzzy_match("hx", "Xtra Ham"));
+    }
+
+    #[test]
+    fn test_filter_editing_and_navigation() {
+        let mut filter = None;
+        assert!(!edit_filter(&mut filter, &InputEvent::Char('x')));
+        assert!(edit_filter(&mut filter, &InputEvent::Char('/')));
+        assert!(edit_filter(&mut filter, &InputEvent::Char('v')));
+        assert_eq!(filter.as_deref(), Some("v"));
+        assert!(edit_filter(&mut filter, &InputEvent::Back));
+        assert_eq!(filter.as_deref(), Some(""));
+        assert!(edit_filter(&mut filter, &InputEvent::Escape));
+        assert_eq!(filter, None);
+
+        let rows = [1, 4, 6];
+        assert_eq!(snap_row(&rows, 2), 1);
+        assert_eq!(step_row(&rows, 6, true), 1);
+        assert_eq!(step_row(&rows, 1, false), 6);
+        assert_eq!(page_row(&rows, 1, 5, true), 6);
+    }
+}
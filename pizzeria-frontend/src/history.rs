@@ -0,0 +1,391 @@
+//Bestellhistorie: Einträge aus GET /transactions, neueste zuerst
+use crate::customer::OrderType;
+use crate::pricing::format_signed_price;
+use crate::table::{Table, TableCell, TableRow};
+use crate::table::Align::Right;
+use crate::table_menu::TableMenu;
+use crate::transactions::{split_transaction_fields, split_transaction_items};
+
+pub const HISTORY_PAGE_SIZE: usize = 10;
+//Bestellart-Feld einer Storno-Buchung (siehe Backend)
+const REFUND_TYPE: &str = "refund";
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct HistoryFilter {
+    pub date: Option<String>,       //Präfix des Zeitstempels, z.B. "2025-01-31" oder "2025-01"
+    pub text: Option<String>,
+}
+
+impl HistoryFilter {
+
+    //leere Eingaben bedeuten "kein Filter"
+    pub fn from_input(date: &str, text: &str) -> Self {
+        let non_empty = |text: &str| Some(text.trim().to_string()).filter(|text| !text.is_empty());
+        HistoryFilter { date: non_empty(date), text: non_empty(text) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.date.is_none() && self.text.is_none()
+    }
+
+    //Pfad inkl. Query für GET /transactions; page beginnt bei 1
+    pub fn request_path(&self, page: usize) -> String {
+        let mut path = format!("/transactions?page={page}&per_page={HISTORY_PAGE_SIZE}");
+        if let Some(date) = &self.date {
+            path.push_str(&format!("&date={}", urlencoding::encode(date)));
+        }
+        if let Some(text) = &self.text {
+            path.push_str(&format!("&q={}", urlencoding::encode(text)));
+        }
+        path
+    }
+
+    //Umsatzbericht für denselben Zeitraum
+    pub fn report_path(&self) -> String {
+        match &self.date {
+            Some(date) => format!("/reports/sales?date={}", urlencoding::encode(date)),
+            None => String::from("/reports/sales"),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(date) = &self.date {
+            parts.push(format!("Datum {date}"));
+        }
+        if let Some(text) = &self.text {
+            parts.push(format!("Suche \"{text}\""));
+        }
+        parts.join(", ")
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct HistoryEntry {
+    pub number: u32,
+    pub timestamp: String,
+    pub price_cents: Option<i64>,       //None = Preisfeld nicht lesbar, negativ bei Stornos
+    pub name: String,
+    pub note: String,                   //bei Stornos der Grund
+    pub order_type: Option<OrderType>,  //nur bei Bestellungen mit Kundendaten
+    pub refund_of: Option<u32>,         //Storno-Buchung zu dieser Bestellnummer
+    pub refunded_by: Option<u32>,       //Bestellung wurde mit dieser Nummer storniert
+    pub customer: String,
+    pub phone: String,
+    pub address: String,
+}
+
+impl HistoryEntry {
+
+    // Format: <Nr>;<Storno-Nr.>;Zeitstempel;Preis;Name[;Notiz[;Bestellart;Kunde;Telefon;Adresse]]
+    // Stornos: ...;Name;Grund;refund;<Nr. der stornierten Bestellung>
+    pub fn parse(line: &str) -> Result<HistoryEntry, String> {
+        let mut fields = split_transaction_fields(line).into_iter();
+
+        let number_text = fields.next().unwrap_or_default();
+        let number = number_text.trim().parse::<u32>()
+            .map_err(|_| format!("Ungültige Bestellnummer '{number_text}'"))?;
+        let refunded_by = fields.next().and_then(|refund| refund.trim().parse::<u32>().ok());
+        let timestamp = fields.next().unwrap_or_default();
+        let price_cents = fields.next().and_then(|price| parse_eur_cents(&price));
+        let name = fields.next()
+            .ok_or_else(|| format!("Bestellung {number}: Name fehlt"))?;
+        let note = fields.next().unwrap_or_default();
+        let type_code = fields.next().unwrap_or_default();
+
+        if type_code == REFUND_TYPE {
+            let refund_of = fields.next().and_then(|original| original.trim().parse::<u32>().ok());
+            return Ok(HistoryEntry { number, timestamp, price_cents, name, note, refund_of, ..Default::default() });
+        }
+        let order_type = OrderType::ALL.into_iter().find(|t| t.code() == type_code);
+
+        Ok(HistoryEntry {
+            number,
+            timestamp,
+            price_cents,
+            name,
+            note,
+            order_type,
+            refund_of: None,
+            refunded_by,
+            customer: fields.next().unwrap_or_default(),
+            phone: fields.next().unwrap_or_default(),
+            address: fields.next().unwrap_or_default(),
+        })
+    }
+
+    pub fn price_text(&self) -> String {
+        self.price_cents.map(format_signed_price).unwrap_or_else(|| String::from("?"))
+    }
+
+    //Stornos und bereits stornierte Bestellungen können nicht (erneut) storniert werden
+    pub fn refund_blocker(&self) -> Option<String> {
+        if let Some(original) = self.refund_of {
+            return Some(format!("#{} ist ein Storno zu Bestellung #{original}", self.number));
+        }
+        self.refunded_by
+            .map(|refund| format!("Bestellung #{} wurde bereits mit #{refund} storniert", self.number))
+    }
+
+    //Name für die Liste: auf `width` Zeichen gekürzt, stornierte Bestellungen markiert
+    pub fn list_name(&self, width: usize) -> String {
+        let full = self.items().join(", ");
+        let name = if full.chars().count() > width {
+            let short: String = full.chars().take(width.saturating_sub(3)).collect();
+            format!("{short}...")
+        } else {
+            full
+        };
+        match self.refunded_by {
+            Some(_) => format!("{name} [storniert]"),
+            None => name,
+        }
+    }
+
+    //Positionen der Transaktion, z.B. "2x Hawaii, Cola"
+    pub fn items(&self) -> Vec<String> {
+        split_transaction_items(&self.name)
+    }
+
+    pub fn to_detail_menu(&self) -> TableMenu {
+        let mut table = Table::new(vec![]);
+        let row = |left: String, right: String| TableRow::new(vec![
+            TableCell::new(left),
+            TableCell::new_with_alignment(right, Right),
+        ]);
+
+        table.push(row(String::from("Bestellung"), self.number.to_string()));
+        table.push(row(String::from("Datum"), self.timestamp.clone()));
+        table.push(row(String::new(), String::new()));
+        for item in self.items() {
+            table.push(row(item, String::new()));
+        }
+        table.push(row(String::new(), String::new()));
+        table.push(row(String::from("Gesamt"), self.price_text()));
+
+        if let Some(original) = self.refund_of {
+            table.push(row(String::from("Storno zu"), format!("#{original}")));
+        }
+        if let Some(refund) = self.refunded_by {
+            table.push(row(String::from("Storniert durch"), format!("#{refund}")));
+        }
+        if !self.note.is_empty() {
+            let label = if self.refund_of.is_some() { "Grund" } else { "Notiz" };
+            table.push(row(format!("{label}: {}", self.note), String::new()));
+        }
+        if let Some(order_type) = self.order_type {
+            table.push(row(String::from("Bestellart"), order_type.label().to_string()));
+        }
+        for (label, value) in [("Kunde", &self.customer), ("Telefon", &self.phone), ("Adresse", &self.address)] {
+            if !value.is_empty() {
+                table.push(row(label.to_string(), value.clone()));
+            }
+        }
+
+        TableMenu::new(format!("Order #{}", self.number), table)
+    }
+}
+
+//"12,50" -> 1250, "-12,50" -> -1250
+fn parse_eur_cents(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (sign, text) = match text.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, text),
+    };
+    let (euros, cents) = text.split_once(',')?;
+    let euros = euros.parse::<i64>().ok()?;
+    let cents = cents.parse::<i64>().ok().filter(|cents| *cents < 100)?;
+    Some(sign * (euros * 100 + cents))
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct History {
+    pub filter: HistoryFilter,
+    pub page: usize,        //ab 1
+    pub total: usize,       //Treffer über alle Seiten
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+
+    pub fn page_count(&self) -> usize {
+        self.total.div_ceil(HISTORY_PAGE_SIZE).max(1)
+    }
+
+    pub fn has_next_page(&self) -> bool {
+        self.page < self.page_count()
+    }
+
+    pub fn has_previous_page(&self) -> bool {
+        self.page > 1
+    }
+}
+
+// Format: erste Zeile Trefferanzahl, danach je Zeile ein Eintrag
+pub fn parse_history_body(body: &str) -> Result<(usize, Vec<HistoryEntry>), String> {
+    let mut lines = body.lines().filter(|line| !line.trim().is_empty());
+
+    let total = match lines.next() {
+        Some(line) => line.trim().parse::<usize>()
+            .map_err(|_| format!("Ungültige Trefferanzahl '{}'", line.trim()))?,
+        None => 0,
+    };
+    let entries = lines
+        .map(HistoryEntry::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((total, entries))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DailySales {
+    pub date: String,           //"total" für die Gesamtsumme
+    pub orders: u32,
+    pub gross_cents: i64,
+    pub refunds: u32,
+    pub refund_cents: i64,
+    pub net_cents: i64,
+}
+
+// Format je Zeile: Datum#Bestellungen#Umsatz#Stornos#Stornobetrag#Netto (Beträge in Cent)
+pub fn parse_sales_report(body: &str) -> Result<Vec<DailySales>, String> {
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let parts: Vec<&str> = line.split('#').collect();
+            let [date, orders, gross, refunds, refund_cents, net] = parts.as_slice() else {
+                return Err(format!("Ungültige Berichtszeile '{line}'"));
+            };
+            let number = |text: &str| text.trim().parse::<i64>()
+                .map_err(|_| format!("Ungültiger Wert '{text}' in '{line}'"));
+            Ok(DailySales {
+                date: date.to_string(),
+                orders: number(orders)? as u32,
+                gross_cents: number(gross)?,
+                refunds: number(refunds)? as u32,
+                refund_cents: number(refund_cents)?,
+                net_cents: number(net)?,
+            })
+        })
+        .collect()
+}
+
+pub fn sales_report_menu(days: &[DailySales], filter: &HistoryFilter) -> TableMenu {
+    let mut table = Table::new(vec![
+        TableRow::new(vec![
+            TableCell::new(String::from("Datum")),
+            TableCell::new_with_alignment(String::from("Best."), Right),
+            TableCell::new_with_alignment(String::from("Umsatz"), Right),
+            TableCell::new_with_alignment(String::from("Stornos"), Right),
+            // letzte Spalte: Abstand zur rechtsbündigen Vorspalte selbst einfügen
+            TableCell::new_with_alignment(String::from("  Netto"), Right),
+        ]),
+    ]);
+
+    for day in days {
+        let date = if day.date == "total" { String::from("Gesamt") } else { day.date.clone() };
+        table.push(TableRow::new(vec![
+            TableCell::new(date),
+            TableCell::new_with_alignment(day.orders.to_string(), Right),
+            TableCell::new_with_alignment(format_signed_price(day.gross_cents), Right),
+            TableCell::new_with_alignment(format!("{}x {}", day.refunds, format_signed_price(-day.refund_cents)), Right),
+            TableCell::new_with_alignment(format!("  {}", format_signed_price(day.net_cents)), Right),
+        ]));
+    }
+
+    let title = match &filter.date {
+        Some(date) => format!("Sales Report {date}"),
+        None => String::from("Sales Report"),
+    };
+    TableMenu::new(title, table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cart::{Cart, CartEntry};
+    use crate::transactions::escape_transaction_field;
+
+    const BODY: &str = "12\n\
+                        8;;2025-01-02 19:00:00;-15,00;Storno #6: Hawaii;Falsch getippt;refund;6\n\
+                        7;;2025-01-02 18:30:00;21,00;2x Margherita, Cola;Klingeln\\; bitte;delivery;Müller;0176 1;Hauptstr. 1\n\
+                        6;8;2025-01-02 18:00:00;15,00;Hawaii\n";
+
+    #[test]
+    fn test_parse_history_body() {
+        let (total, entries) = parse_history_body(BODY).unwrap();
+
+        assert_eq!(total, 12);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].number, 7);
+        assert_eq!(entries[1].price_cents, Some(2100));
+        assert_eq!(entries[1].note, "Klingeln; bitte");
+        assert_eq!(entries[1].order_type, Some(OrderType::Delivery));
+        assert_eq!(entries[1].items(), vec!["2x Margherita", "Cola"]);
+        assert_eq!(entries[2].order_type, None);
+        assert!(parse_history_body("x\n").is_err());
+    }
+
+    #[test]
+    fn test_items_keep_bundle_names_together() {
+        let mut cart = Cart::default();
+        cart.add(CartEntry::new(String::from("Duo Deal (Hawaii, Cola)"), String::from("bundle#Duo Deal#Hawaii|Cola"), 1500));
+        cart.add(CartEntry::new(String::from("Hawaii (\u{2212}Ham, +Corn)"), String::from("prebuilt#Hawaii#Corn"), 900));
+        let line = format!("9;;2025-01-02 20:00:00;24,00;{}\n", escape_transaction_field(&cart.transaction_name()));
+        let (_, entries) = parse_history_body(&format!("1\n{line}")).unwrap();
+
+        assert_eq!(entries[0].items(), vec!["Duo Deal (Hawaii, Cola)", "Hawaii (\u{2212}Ham, +Corn)"]);
+        assert_eq!(entries[0].list_name(60), "Duo Deal (Hawaii, Cola), Hawaii (\u{2212}Ham, +Corn)");
+    }
+
+    #[test]
+    fn test_history_paging_and_query() {
+        let history = History { page: 1, total: 12, ..Default::default() };
+        assert_eq!(history.page_count(), 2);
+        assert!(history.has_next_page());
+        assert!(!history.has_previous_page());
+
+        assert!(HistoryFilter::from_input(" ", "").is_empty());
+        let filter = HistoryFilter::from_input("2025-01", " Müller & Co ");
+        assert_eq!(filter.request_path(2), "/transactions?page=2&per_page=10&date=2025-01&q=M%C3%BCller%20%26%20Co");
+    }
+
+    #[test]
+    fn test_detail_menu_lists_items_and_customer() {
+        let (_, entries) = parse_history_body(BODY).unwrap();
+        let text = entries[1].to_detail_menu().to_string();
+
+        assert!(text.contains("Order #7"));
+        assert!(text.contains("2x Margherita"));
+        assert!(text.contains("21.00$"));
+        assert!(text.contains("Hauptstr. 1"));
+    }
+
+    #[test]
+    fn test_refund_entries_are_linked() {
+        let (_, entries) = parse_history_body(BODY).unwrap();
+
+        assert_eq!(entries[0].refund_of, Some(6));
+        assert_eq!(entries[0].price_text(), "-15.00$");
+        assert_eq!(entries[2].refunded_by, Some(8));
+        assert_eq!(entries[2].list_name(40), "Hawaii [storniert]");
+        assert_eq!(entries[1].list_name(10), "2x Marg...");
+
+        assert!(entries[0].refund_blocker().is_some());
+        assert!(entries[1].refund_blocker().is_none());
+        assert!(entries[2].refund_blocker().is_some());
+        assert!(entries[0].to_detail_menu().to_string().contains("Grund: Falsch getippt"));
+    }
+
+    #[test]
+    fn test_parse_sales_report() {
+        let days = parse_sales_report("2025-01-02#2#3600#1#1500#2100\ntotal#2#3600#1#1500#2100").unwrap();
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].net_cents, 2100);
+        assert!(sales_report_menu(&days, &HistoryFilter::default()).to_string().contains("1x -15.00$"));
+        assert!(parse_sales_report("2025-01-02#2").is_err());
+    }
+}
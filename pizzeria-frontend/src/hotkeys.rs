@@ -0,0 +1,85 @@
+//Hotkeys für Menüzeilen: jede Zeile bekommt eine eindeutige Taste, bevorzugt den
+//Anfangsbuchstaben eines Wortes, sonst einen weiteren Buchstaben oder eine Ziffer
+use crate::table::Table;
+
+//Spalte mit der Kennung "X:", Spalte danach mit dem Text der Zeile
+const KEY_COLUMN: usize = 1;
+const LABEL_COLUMN: usize = 2;
+
+//reserved = Tasten, die das Menü schon für Befehle nutzt (z.B. 'v' im Order-Menü)
+pub fn assign_hotkeys(labels: &[&str], reserved: &[char]) -> Vec<Option<char>> {
+    let mut used: Vec<char> = reserved.iter().map(char::to_ascii_uppercase).collect();
+
+    labels.iter()
+        .map(|label| {
+            let initials = label.split(|c: char| !c.is_ascii_alphanumeric()).filter_map(|word| word.chars().next());
+            let letters = label.chars().filter(char::is_ascii_alphanumeric);
+            let fallback = ('1'..='9').chain('A'..='Z');
+
+            let key = initials.chain(letters).chain(fallback)
+                .map(|c| c.to_ascii_uppercase())
+                .find(|c| !used.contains(c));
+            used.extend(key);
+            key
+        })
+        .collect()
+}
+
+//Zeilen mit einer Kennung "…:" bekommen ihren Hotkey; Platzhalter wie "-" bleiben
+pub fn label_hotkeys(table: &mut Table, reserved: &[char]) {
+    let labelled: Vec<usize> = (0..table.len())
+        .filter(|row| table.cell_text(*row, KEY_COLUMN).is_some_and(|key| key.ends_with(':')))
+        .collect();
+    let labels: Vec<String> = labelled.iter()
+        .map(|row| table.cell_text(*row, LABEL_COLUMN).unwrap_or_default().to_string())
+        .collect();
+    let keys = assign_hotkeys(&labels.iter().map(String::as_str).collect::<Vec<_>>(), reserved);
+
+    for (row, key) in labelled.into_iter().zip(keys) {
+        if let Some(cell) = table.rows_mut()[row].cells_mut().get_mut(KEY_COLUMN) {
+            *cell.text_mut() = key.map(|key| format!("{key}:")).unwrap_or_default();
+        }
+    }
+}
+
+//Zeile zum Hotkey, Groß-/Kleinschreibung egal
+pub fn hotkey_row(table: &Table, key: char) -> Option<usize> {
+    let label = format!("{}:", key.to_ascii_uppercase());
+    (0..table.len()).find(|row| table.cell_text(*row, KEY_COLUMN) == Some(label.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{TableCell, TableRow};
+
+    #[test]
+    fn test_assign_hotkeys_without_collisions() {
+        let keys = assign_hotkeys(&["Pepperoni", "Pineapple", "Pizza Veggie", "Onions", "Custom Pizza"], &['v']);
+
+        assert_eq!(keys, vec![Some('P'), Some('I'), Some('Z'), Some('O'), Some('C')]);
+        assert_eq!(assign_hotkeys(&["Ham", "Ham", "Ham"], &[]), vec![Some('H'), Some('A'), Some('M')]);
+    }
+
+    #[test]
+    fn test_label_and_find_hotkeys() {
+        let row = |key: &str, label: &str| TableRow::new(vec![
+            TableCell::new(" ".into()),
+            TableCell::new(key.into()),
+            TableCell::new(label.into()),
+        ]);
+        let mut table = Table::new(vec![
+            row("1:", "Order"),
+            row("2:", "Order History"),
+            row("-", "Keine Einträge"),
+            row("4:", "Quit"),
+        ]);
+
+        label_hotkeys(&mut table, &[]);
+
+        assert_eq!(table.cell_text(1, 1), Some("H:"));
+        assert_eq!(table.cell_text(2, 1), Some("-"));
+        assert_eq!(hotkey_row(&table, 'q'), Some(3));
+        assert_eq!(hotkey_row(&table, 'x'), None);
+    }
+}
@@ -40,30 +40,166 @@ pub fn read_toppings(arguments: &Arguments) -> io::Result<String> {
     Ok(body)
 }
 
-pub fn send_transaction_record(transaction_record: String, arguments: &Arguments) -> io::Result<()> {
+//GET mit Statusprüfung; path inkl. Query, z.B. "/stock", "/inventory/alerts",
+//"/reports/sales?date=2025-01" oder HistoryFilter::request_path
+pub fn get(path: &str, arguments: &Arguments) -> io::Result<String> {
+    let mut stream = TcpStream::connect(arguments.server_address)?;
+
+    let request = RequestBuilder::get()
+        .host(arguments.server_address.to_string())
+        .path(String::from(path))
+        .build();
+
+    write!(stream, "{}", request)?;
+    stream.flush()?;
+
+    let mut reader = BufReader::new(stream);
+    let code = read_status_code(&mut reader)?;
+
+    if !(200..300).contains(&code) {
+        return Err(
+            FrontendError::HttpStatus { code }.into_io(),
+        );
+    }
+
+    parse_http_response_body(reader)
+        .map_err(FrontendError::into_io)
+}
+
+//path = "/stock/consume" (Bestellzeilen) oder "/stock/restock" (<Topping>#<Menge>); Rückgabe: neuer Bestand
+pub fn send_stock_change(path: &str, body: &str, arguments: &Arguments) -> io::Result<String> {
     let mut stream = TcpStream::connect(arguments.server_address)?;
-    let transaction_record_length = transaction_record.len();
 
     let request = RequestBuilder::post()
-        .path(String::from("/transaction"))
+        .path(String::from(path))
         .host(arguments.server_address.to_string())
         .content_type(String::from("text/plain; charset=utf-8"))
-        .content_length(transaction_record_length)
-        .body(transaction_record)
+        .content_length(body.len())
+        .body(String::from(body))
         .build();
 
     stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    let mut reader = BufReader::new(stream);
+    let code = read_status_code(&mut reader)?;
+
+    //400 ungültige Menge, 404 unbekanntes Topping, 409 ausverkauft, 422 ungültige Bestellung
+    if matches!(code, 400 | 404 | 409 | 422) {
+        let message = parse_http_response_body(reader)
+            .map_err(FrontendError::into_io)?;
+        return Err(FrontendError::Rejected { message }.into_io());
+    }
+
+    if !(200..300).contains(&code) {
+        return Err(
+            FrontendError::HttpStatus { code }.into_io(),
+        );
+    }
+
+    parse_http_response_body(reader)
+        .map_err(FrontendError::into_io)
+}
+
+//Body = Storno-Grund; Rückgabe: Nummer der Storno-Buchung
+pub fn send_refund(number: u32, reason: &str, arguments: &Arguments) -> io::Result<Option<u32>> {
+    let mut stream = TcpStream::connect(arguments.server_address)?;
 
+    let request = RequestBuilder::post()
+        .path(format!("/transactions/{number}/refund"))
+        .host(arguments.server_address.to_string())
+        .content_type(String::from("text/plain; charset=utf-8"))
+        .content_length(reason.len())
+        .body(String::from(reason))
+        .build();
+
+    stream.write_all(request.as_bytes())?;
     stream.flush()?;
 
     let mut reader = BufReader::new(stream);
+    let code = read_status_code(&mut reader)?;
+
+    //404 unbekannt, 409 bereits storniert, 422 Grund fehlt oder Preis ungültig
+    if matches!(code, 404 | 409 | 422) {
+        let message = parse_http_response_body(reader)
+            .map_err(FrontendError::into_io)?;
+        return Err(FrontendError::Rejected { message }.into_io());
+    }
+
+    if !(200..300).contains(&code) {
+        return Err(
+            FrontendError::HttpStatus { code }.into_io(),
+        );
+    }
+
+    let body = parse_http_response_body(reader)
+        .map_err(FrontendError::into_io)?;
+
+    Ok(body.trim().parse::<u32>().ok())
+}
+
+pub fn request_order_price(order: &str, arguments: &Arguments) -> io::Result<String> {
+    let mut stream = TcpStream::connect(arguments.server_address)?;
+
+    let request = RequestBuilder::post()
+        .path(String::from("/order/price"))
+        .host(arguments.server_address.to_string())
+        .content_type(String::from("text/plain; charset=utf-8"))
+        .content_length(order.len())
+        .body(String::from(order))
+        .build();
+
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    let mut reader = BufReader::new(stream);
+    let code = read_status_code(&mut reader)?;
+
+    if code == 422 {
+        let message = parse_http_response_body(reader)
+            .map_err(FrontendError::into_io)?;
+        return Err(FrontendError::Rejected { message }.into_io());
+    }
+
+    if !(200..300).contains(&code) {
+        return Err(
+            FrontendError::HttpStatus { code }.into_io(),
+        );
+    }
+
+    parse_http_response_body(reader)
+        .map_err(FrontendError::into_io)
+}
+
+fn read_status_code(reader: &mut impl BufRead) -> io::Result<u16> {
     let mut status_line = String::new();
     reader.read_line(&mut status_line)?;
-    let code = status_line
+    Ok(status_line
         .split_whitespace()
         .nth(1)
         .and_then(|str| str.parse::<u16>().ok())
-        .unwrap_or(0);
+        .unwrap_or(0))
+}
+
+//Rückgabe: vom Backend vergebene Bestellnummer (falls vorhanden)
+pub fn send_transaction_record(transaction_record: String, arguments: &Arguments) -> io::Result<Option<u32>> {
+    let mut stream = TcpStream::connect(arguments.server_address)?;
+    let transaction_record_length = transaction_record.len();
+
+    let request = RequestBuilder::post()
+        .path(String::from("/transaction"))
+        .host(arguments.server_address.to_string())
+        .content_type(String::from("text/plain; charset=utf-8"))
+        .content_length(transaction_record_length)
+        .body(transaction_record)
+        .build();
+
+    stream.write_all(request.as_bytes())?;
+
+    stream.flush()?;
+
+    let mut reader = BufReader::new(stream);
+    let code = read_status_code(&mut reader)?;
 
     if !(200..300).contains(&code) {
         return Err(
@@ -71,12 +207,10 @@ pub fn send_transaction_record(transaction_record: String, arguments: &Arguments
         );
     }
 
-    let _ = parse_http_response_body(reader)
-        .map_err(FrontendError::into_io)?; //receive empty body to avoid connection closing before server responded
-
-    //todo: validate response status code is successful
+    let body = parse_http_response_body(reader)
+        .map_err(FrontendError::into_io)?;
 
-    Ok(())
+    Ok(body.trim().parse::<u32>().ok())
 }
 
 fn parse_http_response_body(stream: impl Read) -> Result<String, FrontendError> {
@@ -1,50 +1,438 @@
-use std::io::{Read, Stdin};
+use std::io::{ErrorKind, Read, Stdin};
+use std::os::fd::AsRawFd;
+use std::sync::{Mutex, PoisonError};
+use crate::screen;
+use crate::terminal::{suspend, take_resize};
 
+//so lange wird nach einem Esc auf den Rest einer Escape-Sequenz gewartet
+const ESCAPE_TIMEOUT_MS: i32 = 30;
+//mindestens so groß wie der interne Puffer von Stdin (8 KiB), damit read() ihn umgeht
+//und poll() zuverlässig anzeigt, ob noch Bytes ausstehen
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+//Bytes, die zu keinem vollständigen Ereignis gehören, bleiben für den nächsten Aufruf liegen
+static DECODER: Mutex<InputDecoder> = Mutex::new(InputDecoder { pending: Vec::new() });
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub alt: bool,
+    pub ctrl: bool,
+}
+
+impl Modifiers {
+    pub const ALT: Modifiers = Modifiers { shift: false, alt: true, ctrl: false };
+    pub const CTRL: Modifiers = Modifiers { shift: false, alt: false, ctrl: true };
+
+    //xterm-Parameter: 1 + Bitmaske (1 = Shift, 2 = Alt, 4 = Ctrl)
+    fn from_xterm(param: u16) -> Modifiers {
+        let bits = param.saturating_sub(1);
+        Modifiers { shift: bits & 1 != 0, alt: bits & 2 != 0, ctrl: bits & 4 != 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(self.shift || self.alt || self.ctrl)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MouseKind {
+    Press(MouseButton),
+    Release,
+    WheelUp,
+    WheelDown,
+}
+
+//Spalte und Zeile beginnen bei 1 (wie in der SGR-Meldung)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MouseEvent {
+    pub kind: MouseKind,
+    pub column: usize,
+    pub row: usize,
+    pub modifiers: Modifiers,
+}
+
+#[derive(Debug, PartialEq)]
 pub enum InputEvent {
     Up,
     Down,
     Right,
     Left,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
     Enter,
+    Tab,
+    //Shift+Tab
+    BackTab,
     Exit,
     Back,
+    //Esc allein, z.B. um die Suche zu beenden
+    Escape,
+    //F1 bis F12
+    Function(u8),
     Char(char),
+    //Taste mit Shift/Alt/Ctrl, z.B. Ctrl+← oder Alt+x; ohne Modifier steht die Taste direkt
+    Modified {
+        key: Box<InputEvent>,
+        modifiers: Modifiers,
+    },
+    //Klick oder Mausrad (SGR-Mausmodus, siehe terminal.rs)
+    Mouse(MouseEvent),
+    //Ctrl+Z; read_input hält das Programm an und meldet danach Resize
+    Suspend,
+    //Terminalgröße geändert oder Programm fortgesetzt, neu zeichnen
+    Resize,
     Unknown {
         input: Vec<u8>
     },
 }
 
-pub fn read_input(stdin: &mut Stdin, buffer: & mut [u8]) -> Result<InputEvent, Box<dyn std::error::Error>> {
-    let size = stdin.read(buffer)?;
-    let input = &buffer[..size];
-
-    match input {
-        &[3] => {
-            Ok(InputEvent::Exit)
-        }
-        &[13] => {
-            Ok(InputEvent::Enter)
+impl InputEvent {
+    fn with_modifiers(self, modifiers: Modifiers) -> InputEvent {
+        if modifiers.is_empty() {
+            return self;
         }
-        &[27, 91, 65] => {
-            Ok(InputEvent::Up)
+        match self {
+            // Alt kommt als eigenes Esc-Präfix vor einer Sequenz, die schon Modifier tragen kann
+            InputEvent::Modified { key, modifiers: inner } => InputEvent::Modified {
+                key,
+                modifiers: Modifiers {
+                    shift: inner.shift || modifiers.shift,
+                    alt: inner.alt || modifiers.alt,
+                    ctrl: inner.ctrl || modifiers.ctrl,
+                },
+            },
+            key => InputEvent::Modified { key: Box::new(key), modifiers },
         }
-        &[27, 91, 66] => {
-            Ok(InputEvent::Down)
+    }
+}
+
+enum Decoded {
+    //Ereignis und Anzahl der verbrauchten Bytes
+    Event(InputEvent, usize),
+    //Sequenz bricht ab, auf weitere Bytes warten
+    Incomplete,
+}
+
+#[derive(Default)]
+pub struct InputDecoder {
+    pending: Vec<u8>,
+}
+
+impl InputDecoder {
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    //nächstes vollständiges Ereignis; None, wenn nichts ansteht oder die Sequenz unvollständig ist
+    pub fn next_event(&mut self) -> Option<InputEvent> {
+        match decode(&self.pending)? {
+            Decoded::Event(event, length) => {
+                self.pending.drain(..length);
+                Some(event)
+            }
+            Decoded::Incomplete => None,
         }
-        &[27, 91, 67] => {
-            Ok(InputEvent::Right)
+    }
+
+    //unvollständigen Rest auswerten, wenn nichts mehr nachkommt (z.B. Esc allein)
+    pub fn flush(&mut self) -> Option<InputEvent> {
+        let event = match self.pending.as_slice() {
+            [] => return None,
+            [27] => InputEvent::Escape,
+            [27, c @ (b'[' | b'O')] => InputEvent::Char(*c as char).with_modifiers(Modifiers::ALT),
+            rest => InputEvent::Unknown { input: rest.to_vec() },
+        };
+        self.pending.clear();
+        Some(event)
+    }
+}
+
+fn decode(bytes: &[u8]) -> Option<Decoded> {
+    let first = *bytes.first()?;
+    let event = match first {
+        27 => return Some(decode_escape(bytes)),
+        3 => InputEvent::Exit,
+        26 => InputEvent::Suspend,
+        13 | 10 => InputEvent::Enter,
+        9 => InputEvent::Tab,
+        8 | 127 => InputEvent::Back,
+        1..=26 => InputEvent::Char((b'a' + first - 1) as char).with_modifiers(Modifiers::CTRL),
+        0 | 28..=31 => InputEvent::Unknown { input: vec![first] },
+        _ => return Some(decode_utf8(bytes)),
+    };
+    Some(Decoded::Event(event, 1))
+}
+
+fn decode_escape(bytes: &[u8]) -> Decoded {
+    match bytes.get(1) {
+        None => Decoded::Incomplete,
+        // Esc Esc: das erste Esc steht allein
+        Some(27) => Decoded::Event(InputEvent::Escape, 1),
+        Some(b'[') => decode_csi(bytes),
+        Some(b'O') => match bytes.get(2) {
+            None => Decoded::Incomplete,
+            Some(&last) => {
+                let event = final_key(last).unwrap_or_else(|| InputEvent::Unknown { input: bytes[..3].to_vec() });
+                Decoded::Event(event, 3)
+            }
+        },
+        // Alt+Taste kommt als Esc vor der Taste
+        Some(_) => match decode(&bytes[1..]) {
+            Some(Decoded::Event(event, length)) => Decoded::Event(event.with_modifiers(Modifiers::ALT), length + 1),
+            _ => Decoded::Incomplete,
+        },
+    }
+}
+
+//Tasten, deren Sequenz mit einem Buchstaben endet (CSI und SS3)
+fn final_key(last: u8) -> Option<InputEvent> {
+    Some(match last {
+        b'A' => InputEvent::Up,
+        b'B' => InputEvent::Down,
+        b'C' => InputEvent::Right,
+        b'D' => InputEvent::Left,
+        b'H' => InputEvent::Home,
+        b'F' => InputEvent::End,
+        b'P' => InputEvent::Function(1),
+        b'Q' => InputEvent::Function(2),
+        b'R' => InputEvent::Function(3),
+        b'S' => InputEvent::Function(4),
+        _ => return None,
+    })
+}
+
+//Tasten der Form Esc [ <Code> ; <Modifier> ~
+fn tilde_key(code: u16) -> Option<InputEvent> {
+    Some(match code {
+        1 | 7 => InputEvent::Home,
+        2 => InputEvent::Insert,
+        3 => InputEvent::Delete,
+        4 | 8 => InputEvent::End,
+        5 => InputEvent::PageUp,
+        6 => InputEvent::PageDown,
+        11..=15 => InputEvent::Function((code - 10) as u8),
+        17..=21 => InputEvent::Function((code - 11) as u8),
+        23 | 24 => InputEvent::Function((code - 12) as u8),
+        _ => return None,
+    })
+}
+
+// Esc [ <Parameter 0x30-0x3F> <Zwischenbytes 0x20-0x2F> <Abschluss 0x40-0x7E>
+fn decode_csi(bytes: &[u8]) -> Decoded {
+    let Some(end) = bytes.iter().skip(2).position(|b| !(0x20..=0x3f).contains(b)).map(|index| index + 2) else {
+        return Decoded::Incomplete;
+    };
+    let sequence = &bytes[..=end];
+    let unknown = || InputEvent::Unknown { input: sequence.to_vec() };
+    if !(0x40..=0x7e).contains(&bytes[end]) {
+        return Decoded::Event(unknown(), end + 1);
+    }
+
+    let params_text = String::from_utf8_lossy(&bytes[2..end]);
+    if let Some(mouse) = params_text.strip_prefix('<') {
+        let event = decode_sgr_mouse(mouse, bytes[end]).unwrap_or_else(unknown);
+        return Decoded::Event(event, end + 1);
+    }
+    // sonstige private Sequenzen werden nicht ausgewertet
+    if params_text.starts_with(['?', '>', '=']) {
+        return Decoded::Event(unknown(), end + 1);
+    }
+    let params: Vec<u16> = params_text.split(';').map(|param| param.parse().unwrap_or(0)).collect();
+    let modifiers = Modifiers::from_xterm(params.get(1).copied().unwrap_or(1));
+
+    let key = match bytes[end] {
+        b'~' => tilde_key(params[0]),
+        b'Z' => Some(InputEvent::BackTab),
+        last => final_key(last),
+    };
+    let event = key.map(|key| key.with_modifiers(modifiers)).unwrap_or_else(unknown);
+    Decoded::Event(event, end + 1)
+}
+
+// Esc [ < <Knopf> ; <Spalte> ; <Zeile> M (gedrückt) bzw. m (losgelassen)
+fn decode_sgr_mouse(params: &str, last: u8) -> Option<InputEvent> {
+    let fields: Vec<usize> = params.split(';').map(|field| field.parse().ok()).collect::<Option<_>>()?;
+    let [code, column, row] = fields[..] else {
+        return None;
+    };
+    // Bits 4/8/16: Shift/Alt/Ctrl; Bewegungen (Bit 32) werden nicht angefordert
+    let modifiers = Modifiers { shift: code & 4 != 0, alt: code & 8 != 0, ctrl: code & 16 != 0 };
+    let kind = match (code & !0b11100, last) {
+        (64, b'M') => MouseKind::WheelUp,
+        (65, b'M') => MouseKind::WheelDown,
+        (0, b'M') => MouseKind::Press(MouseButton::Left),
+        (1, b'M') => MouseKind::Press(MouseButton::Middle),
+        (2, b'M') => MouseKind::Press(MouseButton::Right),
+        (0..=2, b'm') => MouseKind::Release,
+        _ => return None,
+    };
+    Some(InputEvent::Mouse(MouseEvent { kind, column, row, modifiers }))
+}
+
+fn decode_utf8(bytes: &[u8]) -> Decoded {
+    let length = match bytes[0] {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Decoded::Event(InputEvent::Unknown { input: vec![bytes[0]] }, 1),
+    };
+    if bytes.len() < length {
+        return Decoded::Incomplete;
+    }
+    match std::str::from_utf8(&bytes[..length]).ok().and_then(|text| text.chars().next()) {
+        Some(c) => Decoded::Event(InputEvent::Char(c), length),
+        None => Decoded::Event(InputEvent::Unknown { input: bytes[..length].to_vec() }, length),
+    }
+}
+
+//true, wenn innerhalb von timeout_ms Bytes zum Lesen bereitstehen
+fn wait_readable(stdin: &Stdin, timeout_ms: i32) -> bool {
+    let mut poll_fd = libc::pollfd { fd: stdin.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+    unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) > 0 }
+}
+
+//nach Größenänderung oder Ctrl+Z stimmt der Bildschirmpuffer nicht mehr (siehe screen.rs)
+fn resize_event() -> InputEvent {
+    screen::invalidate();
+    InputEvent::Resize
+}
+
+pub fn read_input(stdin: &mut Stdin) -> Result<InputEvent, Box<dyn std::error::Error>> {
+    if take_resize() {
+        return Ok(resize_event());
+    }
+    let mut decoder = DECODER.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut buffer = [0u8; READ_BUFFER_SIZE];
+
+    loop {
+        if let Some(event) = decoder.next_event() {
+            if event == InputEvent::Suspend {
+                suspend();
+                take_resize();
+                return Ok(resize_event());
+            }
+            return Ok(event);
         }
-        &[27, 91, 68] => {
-            Ok(InputEvent::Left)
+        // angefangene Sequenz: kommt nichts mehr, zählt der Rest für sich (Esc allein)
+        if decoder.has_pending() && !wait_readable(stdin, ESCAPE_TIMEOUT_MS)
+            && let Some(event) = decoder.flush() {
+                return Ok(event);
         }
-        &[127] => {
-            Ok(InputEvent::Back)
+
+        let size = match stdin.read(&mut buffer) {
+            Ok(size) => size,
+            // SIGWINCH unterbricht das Lesen
+            Err(error) if error.kind() == ErrorKind::Interrupted => {
+                take_resize();
+                return Ok(resize_event());
+            }
+            Err(error) => return Err(error.into()),
+        };
+        // Eingabe geschlossen
+        if size == 0 {
+            return Ok(InputEvent::Exit);
         }
-        [c] if (32..=126).contains(c) => Ok(InputEvent::Char(*c as char)),
-        _ => {
-            Ok(InputEvent::Unknown {
-                input: Vec::from(input)
-            })
+        decoder.feed(&buffer[..size]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(bytes: &[u8]) -> Vec<InputEvent> {
+        let mut decoder = InputDecoder::default();
+        decoder.feed(bytes);
+        let mut events = Vec::new();
+        while let Some(event) = decoder.next_event() {
+            events.push(event);
         }
+        events.extend(decoder.flush());
+        events
+    }
+
+    fn modified(key: InputEvent, shift: bool, alt: bool, ctrl: bool) -> InputEvent {
+        InputEvent::Modified { key: Box::new(key), modifiers: Modifiers { shift, alt, ctrl } }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decode_keys_and_sequences_in_one_read() {
+        let events = decode_all(b"\x1b[A\x1b[5~\x1bOP\x1b[15~\x1b[H\x1b[4~\x1b[3~\x1b[Zx\r\x7f");
+
+        assert_eq!(events, vec![
+            InputEvent::Up,
+            InputEvent::PageUp,
+            InputEvent::Function(1),
+            InputEvent::Function(5),
+            InputEvent::Home,
+            InputEvent::End,
+            InputEvent::Delete,
+            InputEvent::BackTab,
+            InputEvent::Char('x'),
+            InputEvent::Enter,
+            InputEvent::Back,
+        ]);
+    }
+
+    #[test]
+    fn test_decode_modifiers_and_utf8() {
+        let events = decode_all("\x1b[1;5D\x1b[1;2A\x1bx\x01\x03\x1aä🍕\x1b\x1b".as_bytes());
+
+        assert_eq!(events, vec![
+            modified(InputEvent::Left, false, false, true),
+            modified(InputEvent::Up, true, false, false),
+            modified(InputEvent::Char('x'), false, true, false),
+            modified(InputEvent::Char('a'), false, false, true),
+            InputEvent::Exit,
+            InputEvent::Suspend,
+            InputEvent::Char('ä'),
+            InputEvent::Char('🍕'),
+            InputEvent::Escape,
+            InputEvent::Escape,
+        ]);
+    }
+
+    #[test]
+    fn test_decode_sgr_mouse() {
+        let mouse = |kind, column, row, modifiers| InputEvent::Mouse(MouseEvent { kind, column, row, modifiers });
+        let events = decode_all(b"\x1b[<0;12;5M\x1b[<0;12;5m\x1b[<65;3;4M\x1b[<18;1;2M\x1b[<35;1;2M");
+
+        assert_eq!(events, vec![
+            mouse(MouseKind::Press(MouseButton::Left), 12, 5, Modifiers::default()),
+            mouse(MouseKind::Release, 12, 5, Modifiers::default()),
+            mouse(MouseKind::WheelDown, 3, 4, Modifiers::default()),
+            mouse(MouseKind::Press(MouseButton::Right), 1, 2, Modifiers::CTRL),
+            InputEvent::Unknown { input: b"\x1b[<35;1;2M".to_vec() },
+        ]);
+    }
+
+    #[test]
+    fn test_wait_for_split_sequences() {
+        let mut decoder = InputDecoder::default();
+        decoder.feed(b"\x1b[");
+        assert_eq!(decoder.next_event(), None);
+        decoder.feed(b"6~\xc3");
+        assert_eq!(decoder.next_event(), Some(InputEvent::PageDown));
+        assert_eq!(decoder.next_event(), None);
+        decoder.feed(b"\xbc");
+        assert_eq!(decoder.next_event(), Some(InputEvent::Char('ü')));
+        assert_eq!(decoder.flush(), None);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::style::Style;
+
+//Zutat unter der Warnschwelle (GET /inventory/alerts)
+#[derive(Debug, PartialEq, Clone)]
+pub struct InventoryAlert {
+    pub name: String,
+    pub amount: u32,
+    pub unit: String,
+    pub threshold: u32,
+}
+
+// Format: <Zutat>#<Bestand>#<g|units>#<Warnschwelle>
+pub fn parse_inventory_alerts(content: &str) -> Result<Vec<InventoryAlert>, String> {
+    let mut alerts = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split('#').map(str::trim).collect();
+        let [name, amount_text, unit, threshold_text] = parts[..] else {
+            return Err(format!("Zeile {}: erwartet 'Zutat#Bestand#Einheit#Warnschwelle'", lineno + 1));
+        };
+        let amount = amount_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Bestand '{}'", lineno + 1, amount_text))?;
+        let threshold = threshold_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültige Warnschwelle '{}'", lineno + 1, threshold_text))?;
+
+        alerts.push(InventoryAlert { name: name.to_string(), amount, unit: unit.to_string(), threshold });
+    }
+
+    Ok(alerts)
+}
+
+//Warnzeile für die Fußzeile, None ohne Warnungen; style = theme().warning
+pub fn alert_line(alerts: &[InventoryAlert], style: &Style) -> Option<String> {
+    if alerts.is_empty() {
+        return None;
+    }
+    let list = alerts.iter()
+        .map(|alert| format!("{} {}/{} {}", alert.name, alert.amount, alert.threshold, alert.unit))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!("{}: {list}", style.paint("Lager niedrig")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Theme;
+
+    #[test]
+    fn test_alert_line_lists_low_ingredients() -> Result<(), Box<dyn std::error::Error>> {
+
+        let alerts = parse_inventory_alerts("Mozzarella#340#g#400\nHam Slices#8#units#10\n")?;
+
+        let warning = Theme::default().warning;
+        assert_eq!(alert_line(&alerts, &warning), Some(String::from("\x1b[1;33mLager niedrig\x1b[0m: Mozzarella 340/400 g, Ham Slices 8/10 units")));
+        assert_eq!(alert_line(&[], &warning), None);
+        assert!(parse_inventory_alerts("Mozzarella#viel#g#400").is_err());
+
+        Ok(())
+    }
+}
@@ -1,37 +1,55 @@
 use std::env;
+use std::env::VarError;
 use std::io::Write;
 use std::io::Stdout;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use crate::error::FrontendError;
 
 pub mod input;
+pub mod filter;
+pub mod hotkeys;
 pub mod render;
 pub mod state;
 pub mod update;
 pub mod custom_toppings;
 pub mod http;
+pub mod pricing;
+pub mod cart;
+pub mod customer;
+pub mod receipt;
+pub mod history;
+pub mod inventory;
 mod ui;
 mod transactions;
 pub mod toppings;
 pub mod table;
 pub mod table_menu;
 pub mod types;
+pub mod terminal;
+pub mod width;
+pub mod style;
+pub mod screen;
 mod error;
 
 pub fn clear_screen(stdout: &mut Stdout) -> Result<(), Box<dyn std::error::Error>> {
     write!(stdout, "\x1B[2J\x1B[1;1H")?;
     stdout.flush()?;
+    screen::invalidate();
     Ok(())
 }
 
 pub const BACKEND_HOST_KEY: &str = "PIZZERIA_FRONTEND_BACKEND_HOST";
 pub const BACKEND_PORT_KEY: &str = "PIZZERIA_FRONTEND_BACKEND_PORT";
+//Datei oder Gerät (z.B. /dev/usb/lp0) für ESC/POS-Bons
+pub const RECEIPT_PATH_KEY: &str = "PIZZERIA_FRONTEND_RECEIPT_PATH";
 const BACKEND_HOST_DEFAULT: Ipv4Addr = Ipv4Addr::new(127, 0, 0, 1);
 const BACKEND_PORT_DEFAULT: u16 = 3333;
 
 #[derive(Debug)]
 pub struct Arguments {
     pub server_address: SocketAddr,
+    pub receipt_path: Option<PathBuf>,
 }
 
 pub fn parse_arguments() -> Result<Arguments, FrontendError> {
@@ -41,9 +59,28 @@ pub fn parse_arguments() -> Result<Arguments, FrontendError> {
             s.parse::<SocketAddr>()
                 .map_err(|error| FrontendError::InvalidSocketAddr { value: s, source: error})
         }
-        None => Ok(SocketAddr::new(IpAddr::V4(BACKEND_HOST_DEFAULT), BACKEND_PORT_DEFAULT))
+        None => {
+            let host = match env::var(BACKEND_HOST_KEY) {
+                Ok(value) => value.parse::<IpAddr>()
+                    .map_err(|error| FrontendError::InvalidHost { key: BACKEND_HOST_KEY, value, source: error })?,
+                Err(VarError::NotPresent) => IpAddr::V4(BACKEND_HOST_DEFAULT),
+                Err(error) => return Err(FrontendError::NotUnicode { key: BACKEND_HOST_KEY, source: error }),
+            };
+            let port = match env::var(BACKEND_PORT_KEY) {
+                Ok(value) => value.parse::<u16>()
+                    .map_err(|error| FrontendError::InvalidPort { key: BACKEND_PORT_KEY, value, source: error })?,
+                Err(VarError::NotPresent) => BACKEND_PORT_DEFAULT,
+                Err(error) => return Err(FrontendError::NotUnicode { key: BACKEND_PORT_KEY, source: error }),
+            };
+            Ok(SocketAddr::new(host, port))
+        }
     }?;
+    let receipt_path = env::var_os(RECEIPT_PATH_KEY)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+
     Ok(Arguments {
-        server_address
+        server_address,
+        receipt_path,
     })
 }
@@ -1,6 +1,7 @@
 use std::io::{Stdin, Stdout, Write};
 use std::fs;
 use pizzeria_frontend::clear_screen;
+use pizzeria_frontend::hotkeys::assign_hotkeys;
 use pizzeria_frontend::table::{Table, TableCell, TableRow};
 use pizzeria_frontend::table_menu::TableMenu;
 use pizzeria_frontend::types::{parse_prebuild_pizza, parse_toppings, Pizza, Topping};
@@ -112,14 +113,18 @@ fn order_custom_pizza(stdout: &mut Stdout, stdin: &Stdin, available_toppings: &[
         base_price: 8,
     };
 
+    // 'Q' beendet die Auswahl
+    let names: Vec<&str> = available_toppings.iter().map(|topping| topping.name.as_str()).collect();
+    let keys = assign_hotkeys(&names, &['q']);
+
     loop {
         clear_screen(stdout)?;
 
         let title_text = String::from("Choose your toppings");
         let mut table = Table::new(vec![]);
 
-        for topping in available_toppings.iter() {
-            let shortname = topping.shortname();
+        for (topping, key) in available_toppings.iter().zip(&keys) {
+            let shortname = key.unwrap_or(' ');
             let name = &topping.name;
             let price = &topping.price;
             table.push(TableRow::new( vec![
@@ -136,15 +141,9 @@ fn order_custom_pizza(stdout: &mut Stdout, stdin: &Stdin, available_toppings: &[
         stdin.read_line(&mut input).unwrap();
         let input= input.trim().chars().next().expect("Input must not be empty!");
 
-        let mut selected_topping: Option<Topping> = None;
-        for topping in available_toppings.iter() {
-            let shortname_uppercase = topping.shortname();
-            let shortname_lowercase = shortname_uppercase.to_lowercase().next().unwrap();
-            if shortname_uppercase == input || shortname_lowercase == input {
-                selected_topping = Some(Clone::clone(topping));
-                break;
-            }
-        }
+        let selected_topping = keys.iter()
+            .position(|key| *key == Some(input.to_ascii_uppercase()))
+            .map(|index| Clone::clone(&available_toppings[index]));
 
         if let Some(topping) = selected_topping {
             pizza.toppings.push(topping);
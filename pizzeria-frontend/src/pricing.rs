@@ -0,0 +1,155 @@
+use std::io::ErrorKind;
+use crate::Arguments;
+use crate::http::request_order_price;
+
+//Grundpreis einer Custom-Pizza in Euro
+pub const CUSTOM_BASE_PRICE: u32 = 6;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PricingScope {
+    Prebuilt,
+    Custom,
+    Item,
+    All,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PricingRule {
+    pub name: String,
+    pub percent: u32,
+    pub scope: PricingScope,
+}
+
+impl PricingRule {
+    pub fn covers(&self, scope: PricingScope) -> bool {
+        matches!(self.scope, PricingScope::All) || self.scope == scope
+    }
+
+    pub fn apply(&self, cents: u32) -> u32 {
+        cents - cents * self.percent / 100
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} -{}%", self.name, self.percent)
+    }
+}
+
+// Antwort von GET /pricing: leer oder <Name>#<Prozent>#<prebuilt|custom|item|all>
+pub fn parse_active_pricing_rule(content: &str) -> Result<Option<PricingRule>, String> {
+    let line = content.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let mut split = line.split('#');
+    let name = split.next().unwrap_or("").trim();
+    let percent_text = split.next()
+        .ok_or_else(|| String::from("Rabatt fehlt"))?;
+    let percent = percent_text.trim().parse::<u32>()
+        .map_err(|_| format!("Ungültiger Rabatt '{percent_text}'"))?;
+    let scope = match split.next().map(str::trim) {
+        Some("prebuilt") => PricingScope::Prebuilt,
+        Some("custom") => PricingScope::Custom,
+        Some("item") => PricingScope::Item,
+        Some("all") => PricingScope::All,
+        other => return Err(format!("Ungültiger Bereich '{}'", other.unwrap_or(""))),
+    };
+
+    Ok(Some(PricingRule { name: name.to_string(), percent, scope }))
+}
+
+pub fn discounted_cents(cents: u32, scope: PricingScope, rule: Option<&PricingRule>) -> u32 {
+    match rule {
+        Some(rule) if rule.covers(scope) => rule.apply(cents),
+        _ => cents,
+    }
+}
+
+pub fn format_price(cents: u32) -> String {
+    format!("{}.{:02}$", cents / 100, cents % 100)
+}
+
+//Stornos und Netto-Umsätze können negativ sein
+pub fn format_signed_price(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{sign}{}", format_price(cents.unsigned_abs() as u32))
+}
+
+//Preise vom Backend (eine Zeile je Bestellzeile); verkauft wird nur zu bestätigten Preisen.
+//Lehnt das Backend die Bestellung ab (z.B. Topping-Limits), ist es nicht erreichbar oder passt
+//die Antwort nicht zu den lokal berechneten Zeilen, wird eine Meldung zurückgegeben.
+pub fn quote_order(order: &str, local_cents: &[u32], arguments: &Arguments) -> Result<Vec<u32>, String> {
+    let body = match request_order_price(order, arguments) {
+        Ok(body) => body,
+        Err(error) if error.kind() == ErrorKind::InvalidInput => return Err(error.to_string()),
+        Err(error) => return Err(format!("Preis konnte nicht vom Backend bestätigt werden: {error}")),
+    };
+    parse_quoted_prices(&body, local_cents.len())
+}
+
+//genau eine Preiszeile (Cent) je Bestellzeile
+fn parse_quoted_prices(body: &str, expected: usize) -> Result<Vec<u32>, String> {
+    let prices = body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse::<u32>().map_err(|_| format!("Ungültiger Preis vom Backend: '{line}'")))
+        .collect::<Result<Vec<_>, _>>()?;
+    if prices.len() != expected {
+        return Err(format!("Backend lieferte {} Preise für {} Bestellzeilen", prices.len(), expected));
+    }
+    Ok(prices)
+}
+
+pub fn quote_order_line(order_line: &str, local_cents: u32, arguments: &Arguments) -> Result<u32, String> {
+    quote_order(order_line, &[local_cents], arguments).map(|prices| prices[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_prices_requires_one_price_per_line() {
+        assert_eq!(parse_quoted_prices("1200\n300\n", 2), Ok(vec![1200, 300]));
+        assert_eq!(parse_quoted_prices("1200\n", 2), Err(String::from("Backend lieferte 1 Preise für 2 Bestellzeilen")));
+        assert!(parse_quoted_prices("1200\nviel\n", 2).is_err());
+    }
+
+    #[test]
+    fn test_parse_active_pricing_rule() -> Result<(), Box<dyn std::error::Error>> {
+
+        let rule = parse_active_pricing_rule("Happy Hour#20#prebuilt")?;
+
+        assert_eq!(rule, Some(PricingRule {
+            name: String::from("Happy Hour"),
+            percent: 20,
+            scope: PricingScope::Prebuilt,
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_no_active_pricing_rule() -> Result<(), Box<dyn std::error::Error>> {
+
+        assert_eq!(parse_active_pricing_rule("")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_discount_only_applies_to_covered_scope() {
+
+        let rule = PricingRule { name: String::from("Happy Hour"), percent: 20, scope: PricingScope::Prebuilt };
+
+        assert_eq!(discounted_cents(1500, PricingScope::Prebuilt, Some(&rule)), 1200);
+        assert_eq!(discounted_cents(1500, PricingScope::Custom, Some(&rule)), 1500);
+        assert_eq!(discounted_cents(1500, PricingScope::Custom, None), 1500);
+    }
+
+    #[test]
+    fn test_format_price() {
+        assert_eq!(format_price(1280), "12.80$");
+        assert_eq!(format_price(5), "0.05$");
+    }
+}
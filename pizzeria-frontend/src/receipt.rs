@@ -0,0 +1,262 @@
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use crate::cart::Cart;
+use crate::customer::CustomerDetails;
+use crate::pricing::format_price;
+use crate::table::{Table, TableCell, TableRow};
+use crate::table::Align::Right;
+use crate::table_menu::TableMenu;
+
+pub const SHOP_NAME: &str = "Salvatores Pizza";
+//Zeichen pro Zeile bei 80mm-Bondruckern (Font A, 42 Spalten)
+pub const RECEIPT_WIDTH: usize = 42;
+
+const ESC: u8 = 0x1B;
+const GS: u8 = 0x1D;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReceiptLine {
+    pub name: String,
+    pub details: Vec<String>,
+    pub total_cents: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Receipt {
+    pub order_number: Option<u32>,      //None = Backend nicht erreichbar
+    pub timestamp: String,
+    pub lines: Vec<ReceiptLine>,
+    pub delivery_fee_cents: Option<u32>,
+    pub total_cents: u32,
+    pub note: Option<String>,
+    pub customer: CustomerDetails,
+}
+
+impl Receipt {
+
+    pub fn from_cart(cart: &Cart, delivery_fee_cents: Option<u32>, customer: &CustomerDetails, order_number: Option<u32>, timestamp: String) -> Self {
+        let lines = cart.entries.iter()
+            .map(|entry| ReceiptLine {
+                name: entry.display_name(),
+                details: entry.details.clone(),
+                total_cents: entry.total_cents(),
+            })
+            .collect();
+
+        Receipt {
+            order_number,
+            timestamp,
+            lines,
+            delivery_fee_cents,
+            total_cents: cart.total_cents() + delivery_fee_cents.unwrap_or(0),
+            note: cart.note.clone(),
+            customer: customer.clone(),
+        }
+    }
+
+    fn order_number_text(&self) -> String {
+        match self.order_number {
+            Some(number) => number.to_string(),
+            None => String::from("offline"),
+        }
+    }
+
+    //Kundendaten-Zeilen, leere Felder entfallen
+    fn customer_lines(&self) -> Vec<String> {
+        let customer = &self.customer;
+        let mut lines = vec![customer.order_type.label().to_string()];
+        if !customer.name.is_empty() {
+            lines.push(customer.name.clone());
+        }
+        if !customer.phone.is_empty() {
+            lines.push(format!("Tel. {}", customer.phone));
+        }
+        if let Some(address) = &customer.address {
+            lines.push(address.clone());
+        }
+        lines
+    }
+
+    //Klartext-Bon für das Terminal
+    pub fn to_table_menu(&self) -> TableMenu {
+        let mut table = Table::new(vec![]);
+        let row = |left: String, right: String| TableRow::new(vec![
+            TableCell::new(left),
+            TableCell::new_with_alignment(right, Right),
+        ]);
+
+        table.push(row(String::from("Bon-Nr."), self.order_number_text()));
+        table.push(row(String::from("Datum"), self.timestamp.clone()));
+        table.push(row(String::new(), String::new()));
+
+        for line in &self.lines {
+            table.push(row(line.name.clone(), format_price(line.total_cents)));
+            for detail in &line.details {
+                table.push(row(format!("  {detail}"), String::new()));
+            }
+        }
+        if let Some(fee) = self.delivery_fee_cents {
+            table.push(row(String::from("Delivery fee"), format_price(fee)));
+        }
+
+        table.push(row(String::new(), String::new()));
+        table.push(row(String::from("Gesamt"), format_price(self.total_cents)));
+
+        if let Some(note) = &self.note {
+            table.push(row(format!("Notiz: {note}"), String::new()));
+        }
+        for line in self.customer_lines() {
+            table.push(row(line, String::new()));
+        }
+
+        TableMenu::new(String::from(SHOP_NAME), table)
+    }
+
+    //ESC/POS-Bytestrom: Init, Codepage PC858, Kopf, Positionen, Summe, Schnitt
+    pub fn to_escpos(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        out.extend_from_slice(&[ESC, b'@']);
+        out.extend_from_slice(&[ESC, b't', 19]);
+
+        //Kopf zentriert, fett, doppelte Größe
+        out.extend_from_slice(&[ESC, b'a', 1, ESC, b'E', 1, GS, b'!', 0x11]);
+        push_line(&mut out, SHOP_NAME);
+        out.extend_from_slice(&[GS, b'!', 0, ESC, b'E', 0]);
+        push_line(&mut out, &format!("Bon-Nr. {}", self.order_number_text()));
+        push_line(&mut out, &self.timestamp);
+        out.extend_from_slice(&[ESC, b'a', 0]);
+
+        push_line(&mut out, &"-".repeat(RECEIPT_WIDTH));
+        for line in &self.lines {
+            push_line(&mut out, &two_columns(&line.name, &format_price(line.total_cents)));
+            for detail in &line.details {
+                push_line(&mut out, &format!("  {detail}"));
+            }
+        }
+        if let Some(fee) = self.delivery_fee_cents {
+            push_line(&mut out, &two_columns("Delivery fee", &format_price(fee)));
+        }
+        push_line(&mut out, &"-".repeat(RECEIPT_WIDTH));
+
+        out.extend_from_slice(&[ESC, b'E', 1]);
+        push_line(&mut out, &two_columns("Gesamt", &format_price(self.total_cents)));
+        out.extend_from_slice(&[ESC, b'E', 0]);
+
+        if let Some(note) = &self.note {
+            push_line(&mut out, &format!("Notiz: {note}"));
+        }
+        for line in self.customer_lines() {
+            push_line(&mut out, &line);
+        }
+
+        //Vorschub und Teilschnitt
+        out.extend_from_slice(&[GS, b'V', 66, 3]);
+        out
+    }
+}
+
+//Name links, Preis rechtsbündig; zu lange Namen werden gekürzt
+fn two_columns(left: &str, right: &str) -> String {
+    let right_len = right.chars().count();
+    let space = RECEIPT_WIDTH.saturating_sub(right_len + 1);
+    let left: String = left.chars().take(space).collect();
+    let padding = RECEIPT_WIDTH - left.chars().count() - right_len;
+    format!("{left}{}{right}", " ".repeat(padding))
+}
+
+fn push_line(out: &mut Vec<u8>, text: &str) {
+    out.extend(encode_pc858(text));
+    out.push(b'\n');
+}
+
+//Codepage PC858 (Westeuropa inkl. €), nicht darstellbare Zeichen als '?'
+pub fn encode_pc858(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            'ä' => 0x84,
+            'ö' => 0x94,
+            'ü' => 0x81,
+            'Ä' => 0x8E,
+            'Ö' => 0x99,
+            'Ü' => 0x9A,
+            'ß' => 0xE1,
+            '€' => 0xD5,
+            '\u{2212}' => b'-',
+            _ => b'?',
+        })
+        .collect()
+}
+
+//Datei wird fortgeschrieben, damit auch Druckergeräte (z.B. /dev/usb/lp0) funktionieren
+pub fn write_receipt(path: &Path, receipt: &Receipt) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(&receipt.to_escpos())?;
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cart::CartEntry;
+    use crate::customer::OrderType;
+
+    fn receipt() -> Receipt {
+        let mut cart = Cart::default();
+        cart.add(CartEntry::new(String::from("Hawaii"), String::from("prebuilt#Hawaii"), 1500)
+            .with_details(vec![String::from("A-Pineapple"), String::from("Ham")]));
+        cart.add(CartEntry::new(String::from("Cola"), String::from("item#Cola"), 300));
+        cart.note = Some(String::from("Klingeln"));
+
+        let customer = CustomerDetails {
+            order_type: OrderType::Delivery,
+            name: String::from("Müller"),
+            phone: String::from("0176 123456"),
+            address: Some(String::from("Hauptstr. 1")),
+        };
+        Receipt::from_cart(&cart, Some(300), &customer, Some(17), String::from("2025-01-01 12:00:00"))
+    }
+
+    #[test]
+    fn test_plain_text_receipt() {
+        let text = receipt().to_table_menu().to_string();
+
+        assert!(text.contains(SHOP_NAME));
+        assert!(text.contains("Bon-Nr."));
+        assert!(text.contains("17"));
+        assert!(text.contains("  Ham"));
+        assert!(text.contains("21.00$"));
+        assert!(text.contains("Tel. 0176 123456"));
+    }
+
+    #[test]
+    fn test_escpos_receipt() {
+        let bytes = receipt().to_escpos();
+
+        assert!(bytes.starts_with(&[ESC, b'@', ESC, b't', 19]));
+        assert!(bytes.ends_with(&[GS, b'V', 66, 3]));
+
+        let line = two_columns("Hawaii", "15.00$");
+        assert_eq!(line.len(), RECEIPT_WIDTH);
+        assert!(bytes.windows(line.len()).any(|window| window == line.as_bytes()));
+        assert!(bytes.windows(3).any(|window| window == [b'M', 0x81, b'l']));
+    }
+
+    #[test]
+    fn test_write_receipt_appends_to_file() -> Result<(), Box<dyn std::error::Error>> {
+        let file = tempfile::NamedTempFile::new()?;
+
+        write_receipt(file.path(), &receipt())?;
+        write_receipt(file.path(), &receipt())?;
+
+        let written = std::fs::read(file.path())?;
+        assert_eq!(written.len(), 2 * receipt().to_escpos().len());
+        Ok(())
+    }
+}
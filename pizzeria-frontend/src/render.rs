@@ -1,36 +1,80 @@
-use std::io::{Stdout, Write};
-use crate::table_menu::TableMenu;
+use std::io::Stdout;
+use crate::table_menu::{ScreenRow, TableMenu};
+use crate::width::display_width;
 use crate::state::{MenuIndex, State};
+use crate::inventory::alert_line;
+use crate::style::theme;
+use crate::terminal::{fit_box, terminal_size};
+use crate::screen::present;
 
 pub fn render_menu(
     stdout: &mut Stdout,
-    menu: &TableMenu,
+    menu: &mut TableMenu,
     menu_name: &str,
     selected_row: usize,
     footer: &[&str],
 ) -> Result<(), Box<dyn std::error::Error>> {
-    writeln!(stdout, "\x1B[1J\x1B[1;1H")?;
-    writeln!(stdout, "{menu}")?;
-    writeln!(stdout, "Row: {selected_row}, Menu: {menu_name}")?;
-
-    for line in footer {
-        if !line.is_empty() {
-            writeln!(stdout, "{line}")?;
-        } else {
-            writeln!(stdout)?;
-        }
-    }
+    // aktive Suche als eigene Zeile über der Fußzeile
+    let search = menu.filter().map(|query| format!("Suche: /{query}_ · [Esc] Suche beenden"));
+
+    // Leerzeile oben, Leer- und Statuszeile sowie die Fußzeile bleiben sichtbar
+    let size = terminal_size();
+    let reserved = footer.len() + 3 + usize::from(search.is_some());
+    // Rahmen oben, Titel, Trennlinie und Rahmen unten
+    menu.scroll_into_view(size.rows.saturating_sub(reserved + 4));
+
+    let lines: Vec<String> = menu.to_string().lines().map(String::from).collect();
+    let fitted = fit_box(&lines, size, reserved);
+    menu.set_screen_rows(screen_rows(menu, &fitted));
+
+    // ganzer Frame im Puffer, ausgegeben werden nur die geänderten Zeilen (siehe screen.rs)
+    let mut frame = vec![String::new()];
+    frame.extend(fitted);
+    frame.push(String::new());
+    frame.push(format!("Row: {selected_row}, Menu: {menu_name}"));
+    frame.extend(search);
+    frame.extend(footer.iter().map(|line| line.to_string()));
+    present(stdout, &frame)?;
     Ok(())
 }
 
-pub fn render(stdout: &mut Stdout, state: &State) -> Result<(), Box<dyn std::error::Error>> {
-    let menu = state.current_menu();
-    let (menu_name, footer): (&str, Vec<&str>) = match state.current_menu {
-        MenuIndex::MainMenu => ("MainMenu", vec!["[↑/↓] bewegen · [Enter] auswählen"]),
-        MenuIndex::OrderMenu => ("OrderMenu", vec!["[↑/↓] bewegen · [Enter] auswählen · [Backspace] zurück"]),
-        MenuIndex::EditToppingsMenu => ("EditToppingsMenu", vec!["[↑/↓] bewegen · [Enter] auswählen · [Backspace] zurück"]),
+//Position der Tabellenzeilen auf dem Bildschirm; die Box beginnt in Zeile 2
+//(nach der Leerzeile oben) unter den Leerzeilen, mit denen fit_box zentriert
+fn screen_rows(menu: &TableMenu, fitted: &[String]) -> Vec<ScreenRow> {
+    let top = fitted.iter().take_while(|line| line.is_empty()).count();
+    let box_lines = fitted.len() - top;
+
+    menu.window_lines().into_iter()
+        // von fit_box abgeschnittene Zeilen (die letzte Zeile ist immer der untere Rahmen)
+        .filter(|(line, _)| line + 1 < box_lines)
+        .map(|(line, row)| {
+            let text = &fitted[top + line];
+            let content = text.trim_start();
+            let left = text.len() - content.len() + 1;
+            ScreenRow { line: 2 + top + line, columns: left..left + display_width(content), row }
+        })
+        .collect()
+}
+
+pub fn render(stdout: &mut Stdout, state: &mut State) -> Result<(), Box<dyn std::error::Error>> {
+    let (menu_name, mut footer): (&str, Vec<&str>) = match state.current_menu {
+        MenuIndex::MainMenu => ("MainMenu", vec!["[↑/↓] bewegen · [Enter] auswählen · [/] + Text suchen"]),
+        MenuIndex::OrderMenu => ("OrderMenu", vec!["[↑/↓] bewegen · [Enter] auswählen · [v] vegetarisch/vegan filtern · [/] + Text suchen · [Backspace] zurück"]),
+        MenuIndex::EditToppingsMenu => ("EditToppingsMenu", vec!["[↑/↓] bewegen · [Enter] auswählen · [/] + Text suchen · [Backspace] zurück"]),
+        MenuIndex::CategoryMenu => ("CategoryMenu", vec!["[↑/↓] bewegen · [Enter] in den Warenkorb · [→] Pizza anpassen · [/] + Text suchen · [Backspace] zurück"]),
+        MenuIndex::CartMenu => ("CartMenu", vec!["[↑/↓] bewegen · [+/-] Menge · [x] entfernen · [n] Notiz · [Enter] auswählen · [/] + Text suchen · [Backspace] zurück"]),
+        MenuIndex::HistoryMenu => ("HistoryMenu", vec!["[↑/↓] bewegen · [←/→] Seite · [Enter] Details · [c] stornieren · [s] Umsatz · [f] filtern · [r] Filter zurücksetzen · [/] + Text suchen · [Backspace] zurück"]),
     };
-    render_menu(stdout, menu, menu_name, state.selected_row(), &footer)?;
+    let alert = alert_line(&state.inventory_alerts, &theme().warning);
+    if let Some(alert) = &alert {
+        footer.insert(0, alert);
+    }
+    let load_error = state.load_error_line();
+    if let Some(load_error) = &load_error {
+        footer.insert(0, load_error);
+    }
+    let selected_row = state.selected_row();
+    render_menu(stdout, state.current_menu_mut(), menu_name, selected_row, &footer)?;
 
     Ok(())
 }
@@ -0,0 +1,131 @@
+//Bildschirmpuffer für flackerfreies Zeichnen: der zuletzt ausgegebene Frame bleibt
+//gespeichert, neu geschrieben werden nur Zeilen, die sich geändert haben
+use std::io;
+use std::io::{Stdout, Write};
+use std::sync::Mutex;
+use crate::terminal::{terminal_size, TerminalSize};
+use crate::width::display_width;
+
+static SCREEN: Mutex<Screen> = Mutex::new(Screen::new());
+
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+pub struct Screen {
+    //Bildschirmzeile (ab 1), ab der eine Zeile des letzten Frames steht, und ihr Text
+    lines: Vec<(usize, String)>,
+    size: Option<TerminalSize>,
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Screen {
+    pub const fn new() -> Self {
+        Self { lines: Vec::new(), size: None }
+    }
+
+    //nach fremden Ausgaben (Meldungen, Eingaben, clear_screen) stimmt der Puffer nicht mehr
+    pub fn invalidate(&mut self) {
+        self.lines.clear();
+        self.size = None;
+    }
+
+    //Ausgabe, die den Bildschirm vom letzten auf den neuen Frame bringt; zu lange Zeilen
+    //bricht das Terminal um, sie belegen dann mehrere Bildschirmzeilen
+    pub fn diff(&mut self, frame: &[String], size: TerminalSize) -> String {
+        if self.size != Some(size) {
+            self.invalidate();
+        }
+
+        let mut output = String::from(HIDE_CURSOR);
+        let mut lines = Vec::with_capacity(frame.len());
+        let mut row = 1;
+        for line in frame {
+            let unchanged = self.lines.iter().any(|(old_row, old)| *old_row == row && old == line);
+            if !unchanged {
+                output.push_str(&format!("\x1b[{row};1H{line}\x1b[K"));
+            }
+            lines.push((row, line.clone()));
+            row += display_width(line).div_ceil(size.cols.max(1)).max(1);
+        }
+
+        // Cursor unter den Frame, wie nach der zeilenweisen Ausgabe, und Reste darunter entfernen;
+        // füllt der Frame den Bildschirm, bleibt der Cursor am Ende der letzten Zeile
+        if row <= size.rows {
+            output.push_str(&format!("\x1b[{row};1H\x1b[J"));
+        } else {
+            output.push_str(&format!("\x1b[{};{}H", size.rows, size.cols));
+        }
+        output.push_str(SHOW_CURSOR);
+
+        // ragt der Frame über den Bildschirm hinaus, hat das Terminal gescrollt
+        if row > size.rows + 1 {
+            self.invalidate();
+        } else {
+            self.lines = lines;
+            self.size = Some(size);
+        }
+        output
+    }
+}
+
+pub fn present(stdout: &mut Stdout, frame: &[String]) -> io::Result<()> {
+    let output = SCREEN.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+        .diff(frame, terminal_size());
+    stdout.write_all(output.as_bytes())?;
+    stdout.flush()
+}
+
+pub fn invalidate() {
+    SCREEN.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).invalidate();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: TerminalSize = TerminalSize { cols: 20, rows: 10 };
+
+    fn frame(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_only_changed_lines_are_written() {
+        let mut screen = Screen::new();
+
+        let first = screen.diff(&frame(&["", "> Order", "  Quit"]), SIZE);
+        assert_eq!(first, "\x1b[?25l\x1b[1;1H\x1b[K\x1b[2;1H> Order\x1b[K\x1b[3;1H  Quit\x1b[K\x1b[4;1H\x1b[J\x1b[?25h");
+
+        let second = screen.diff(&frame(&["", "  Order", "> Quit"]), SIZE);
+        assert_eq!(second, "\x1b[?25l\x1b[2;1H  Order\x1b[K\x1b[3;1H> Quit\x1b[K\x1b[4;1H\x1b[J\x1b[?25h");
+
+        let same = screen.diff(&frame(&["", "  Order", "> Quit"]), SIZE);
+        assert_eq!(same, "\x1b[?25l\x1b[4;1H\x1b[J\x1b[?25h");
+    }
+
+    #[test]
+    fn test_redraw_after_invalidate_and_wrapped_lines() {
+        let mut screen = Screen::new();
+        screen.diff(&frame(&["", "Menu"]), SIZE);
+
+        screen.invalidate();
+        let redraw = screen.diff(&frame(&["", "Menu"]), SIZE);
+        assert!(redraw.contains("\x1b[2;1HMenu"));
+
+        // 25 Zeichen bei 20 Spalten belegen zwei Zeilen, die nächste beginnt in Zeile 4
+        let wrapped = screen.diff(&frame(&["", "x".repeat(25).as_str(), "Footer"]), SIZE);
+        assert!(wrapped.contains("\x1b[4;1HFooter"));
+        assert!(wrapped.ends_with("\x1b[5;1H\x1b[J\x1b[?25h"));
+
+        let full: Vec<String> = (1..=10).map(|line| line.to_string()).collect();
+        assert!(screen.diff(&full, SIZE).ends_with("\x1b[10;1H10\x1b[K\x1b[10;20H\x1b[?25h"));
+
+        let resized = screen.diff(&frame(&["", "x".repeat(25).as_str(), "Footer"]), TerminalSize { cols: 30, rows: 10 });
+        assert!(resized.contains("\x1b[3;1HFooter"));
+    }
+}
@@ -2,21 +2,57 @@ use std::io::Write;
 use std::{fs, io};
 use std::io::Stdout;
 use crate::Arguments;
+use crate::cart::Cart;
+use crate::filter::page_row;
+use crate::hotkeys::label_hotkeys;
+use crate::style::{theme, Style};
+use crate::customer::{parse_delivery_settings, DeliverySettings};
+use crate::history::{parse_history_body, History, HistoryEntry, HistoryFilter};
+use crate::inventory::{parse_inventory_alerts, InventoryAlert};
 use crate::table::{Table, TableCell, TableRow};
 use crate::table::Align::Right;
 use crate::table_menu::TableMenu;
-use crate::types::{parse_prebuild_pizza, parse_toppings, Pizza, Topping};
-use crate::http::{read_pizza_prebuilds, read_toppings};
+use crate::types::{apply_stock, parse_bundles, parse_catalog_items, parse_prebuild_pizza, parse_stock, parse_topping_limits, parse_toppings, Bundle, CatalogItem, Category, DietaryFlag, Pizza, Topping, ToppingLimits};
+use crate::http::{get, read_pizza_prebuilds, read_toppings, send_stock_change};
+use crate::pricing::{discounted_cents, format_price, parse_active_pricing_rule, PricingRule, PricingScope};
 
 pub struct State {
-    pub menus: [TableMenu; 3],
+    pub menus: [TableMenu; 6],
     pub current_menu: MenuIndex,
-    pub selected_rows: [usize; 3],
+    pub selected_rows: [usize; 6],
     pub toppings_catalog: Vec<Topping>,
+    pub topping_limits: ToppingLimits,
     pub prebuilt_pizzas: Vec<Pizza>,
+    //Ladefehler der Prebuilt-Pizzen, wird in der Pizza-Kategorie angezeigt
+    pub prebuilt_error: Option<String>,
+    pub catalog_items: Vec<CatalogItem>,
+    pub bundles: Vec<Bundle>,
+    pub order_categories: Vec<OrderCategory>,
+    pub current_category: OrderCategory,
+    pub dietary_filter: Option<DietaryFlag>,
+    pub active_pricing_rule: Option<PricingRule>,
+    pub cart: Cart,
+    pub delivery_settings: DeliverySettings,
+    pub history: History,
+    pub inventory_alerts: Vec<InventoryAlert>,
+    //Fehler beim letzten Laden von Backend-Einstellungen, z.B. "Preisaktion: HTTP 500";
+    //der zuletzt geladene Wert bleibt in Gebrauch
+    pub load_errors: Vec<String>,
     pub pending_fallbacks: Vec<String>,
+    //Lagerbuchungen (/stock/consume), die das Backend noch nicht bestätigt hat
+    pub pending_stock_consumes: Vec<PendingStockConsume>,
 }
 
+//Buchungstext mit Kennung (siehe transactions::format_stock_booking), daher gefahrlos wiederholbar
+#[derive(Debug, Clone)]
+pub struct PendingStockConsume {
+    pub booking: String,
+    pub attempts: u32,
+}
+
+//danach wird die Buchung verworfen und muss von Hand nachgetragen werden
+const MAX_STOCK_CONSUME_ATTEMPTS: u32 = 5;
+
 impl State {
     pub fn current_menu(&self) -> &TableMenu {
         &self.menus[self.current_menu.as_index()]
@@ -34,6 +70,20 @@ impl State {
         self.selected_rows[i] = row;
     }
 
+    //PageUp/PageDown: Auswahl um eine sichtbare Seite verschieben (ohne Umbruch)
+    pub fn move_selection_by_page(&mut self, down: bool) {
+        let page = self.current_menu().page_size();
+        self.move_selection_by(page, down);
+    }
+
+    //Auswahl um `step` sichtbare Zeilen verschieben, z.B. per Mausrad
+    pub fn move_selection_by(&mut self, step: usize, down: bool) {
+        let menu = self.current_menu();
+        let row = page_row(&menu.visible_rows(), self.selected_row(), step, down);
+        self.set_selected_row(row);
+        self.apply_selection_marker();
+    }
+
     pub fn apply_selection_marker(&mut self) {
         let sel = self.selected_row();
         let table = self.current_menu_mut().table_mut();
@@ -41,22 +91,59 @@ impl State {
     }
 
     pub fn refresh_order_menu(&mut self, arguments: &Arguments) {
+        self.load_errors.clear();
+        // vor dem Laden des Bestands, damit nachgeholte Buchungen schon enthalten sind
+        self.retry_pending_stock_consumes(arguments);
         if let Ok(catalog) = load_toppings_from_backend(arguments) {
             self.toppings_catalog = catalog;
         }
+        // ohne Bestandsdaten gelten alle Toppings als unbegrenzt
+        let stock = load_stock_from_backend(arguments).unwrap_or_default();
+        apply_stock(&mut self.toppings_catalog, &stock);
+        self.refresh_inventory_alerts(arguments);
+        match load_topping_limits_from_backend(arguments) {
+            Ok(limits) => self.topping_limits = limits,
+            Err(e) => self.load_errors.push(format!("Topping-Limits: {e}")),
+        }
+        match load_active_pricing_rule_from_backend(arguments) {
+            Ok(rule) => self.active_pricing_rule = rule,
+            Err(e) => self.load_errors.push(format!("Preisaktion: {e}")),
+        }
+        match load_delivery_settings_from_backend(arguments) {
+            Ok(settings) => self.delivery_settings = settings,
+            Err(e) => self.load_errors.push(format!("Lieferung: {e}")),
+        }
 
         let idx = MenuIndex::OrderMenu.as_index();
 
+        // Kategorien unabhängig laden: ein Fehler betrifft nur die eigene Kategorie,
+        // Getränke und Desserts bleiben verkaufbar
         match load_prebuilt_pizzas_from_backend(&self.toppings_catalog, arguments) {
             Ok(pizzas) => {
                 self.prebuilt_pizzas = pizzas;
-                self.menus[idx] = build_order_menu(&self.prebuilt_pizzas);
+                self.prebuilt_error = None;
             }
             Err(e) => {
                 self.prebuilt_pizzas.clear();
-                self.menus[idx] = build_order_menu_error(&e.to_string());
+                self.prebuilt_error = Some(e.to_string());
             }
         }
+        match load_catalog_items_from_backend(arguments) {
+            Ok(items) => self.catalog_items = items,
+            Err(e) => {
+                self.catalog_items.clear();
+                self.load_errors.push(format!("Artikel: {e}"));
+            }
+        }
+        match load_bundles_from_backend(&self.prebuilt_pizzas, &self.catalog_items, arguments) {
+            Ok(bundles) => self.bundles = bundles,
+            Err(e) => {
+                self.bundles.clear();
+                self.load_errors.push(format!("Deals: {e}"));
+            }
+        }
+        self.order_categories = available_order_categories(&self.catalog_items, &self.bundles);
+        self.menus[idx] = build_order_menu(&self.order_categories, self.active_pricing_rule.as_ref(), &self.cart, self.dietary_filter);
 
         let len = self.menus[idx].table_mut().rows_mut().len();
         if self.selected_rows[idx] >= len {
@@ -67,13 +154,192 @@ impl State {
             crate::update::select_row(self.menus[idx].table_mut(), sel_row);
         }
     }
+
+    //Lagerbuchungen nachholen; Meldungen erscheinen mit den Ladefehlern
+    fn retry_pending_stock_consumes(&mut self, arguments: &Arguments) {
+        let mut still_pending = Vec::new();
+
+        for mut pending in self.pending_stock_consumes.drain(..) {
+            pending.attempts += 1;
+            match send_stock_change("/stock/consume", &pending.booking, arguments) {
+                Ok(_) => {}
+                // abgelehnt (z.B. inzwischen ausverkauft): erneutes Senden hilft nicht
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput => {
+                    self.load_errors.push(format!("Lagerbuchung abgelehnt: {e}"));
+                }
+                Err(e) if pending.attempts >= MAX_STOCK_CONSUME_ATTEMPTS => {
+                    self.load_errors.push(format!("Lagerbuchung nach {} Versuchen verworfen: {e}", pending.attempts));
+                }
+                Err(e) => {
+                    self.load_errors.push(format!("Lagerbuchung ausstehend: {e}"));
+                    still_pending.push(pending);
+                }
+            }
+        }
+
+        self.pending_stock_consumes = still_pending;
+    }
+
+    //Fußzeile mit den Ladefehlern, None wenn alles geladen wurde
+    pub fn load_error_line(&self) -> Option<String> {
+        if self.load_errors.is_empty() {
+            return None;
+        }
+        Some(format!("{}: {}", theme().error.paint("Ladefehler"), self.load_errors.join(" · ")))
+    }
+
+    pub fn refresh_inventory_alerts(&mut self, arguments: &Arguments) {
+        self.inventory_alerts = load_inventory_alerts_from_backend(arguments).unwrap_or_default();
+    }
+
+    //Order-Menü ohne Backend-Abfrage neu aufbauen (z.B. nach Änderungen am Warenkorb)
+    pub fn rebuild_order_menu(&mut self) {
+        let idx = MenuIndex::OrderMenu.as_index();
+        self.menus[idx] = build_order_menu(&self.order_categories, self.active_pricing_rule.as_ref(), &self.cart, self.dietary_filter);
+    }
+
+    //Prebuilt-Pizzen, die zum Ernährungsfilter passen (Indizes in prebuilt_pizzas)
+    pub fn visible_prebuilts(&self) -> Vec<usize> {
+        visible_prebuilts(&self.prebuilt_pizzas, self.dietary_filter)
+    }
+
+    pub fn open_cart(&mut self) {
+        let idx = MenuIndex::CartMenu.as_index();
+        self.menus[idx] = build_cart_menu(&self.cart);
+        if self.selected_rows[idx] >= self.menus[idx].table_mut().rows_mut().len() {
+            self.selected_rows[idx] = 0;
+        }
+        self.current_menu = MenuIndex::CartMenu;
+        self.apply_selection_marker();
+    }
+
+    pub fn cart_menu_entry(&self, row: usize) -> Option<CartMenuEntry> {
+        // ein leerer Warenkorb belegt mit dem Hinweis die erste Zeile
+        let entry_rows = self.cart.entries.len().max(1);
+
+        if row < self.cart.entries.len() {
+            Some(CartMenuEntry::Entry(row))
+        } else if row == entry_rows + 1 {
+            Some(CartMenuEntry::Checkout)
+        } else if row == entry_rows + 2 {
+            Some(CartMenuEntry::Note)
+        } else if row == entry_rows + 3 {
+            Some(CartMenuEntry::Clear)
+        } else if row == entry_rows + 4 {
+            Some(CartMenuEntry::Back)
+        } else {
+            None
+        }
+    }
+
+    //Seite der Bestellhistorie laden; bei Fehlern bleibt die Liste leer und zeigt die Meldung
+    pub fn open_history(&mut self, filter: HistoryFilter, page: usize, arguments: &Arguments) {
+        let idx = MenuIndex::HistoryMenu.as_index();
+
+        match load_history_from_backend(&filter, page, arguments) {
+            Ok((total, entries)) => {
+                self.history = History { filter, page, total, entries };
+                self.menus[idx] = build_history_menu(&self.history);
+            }
+            Err(e) => {
+                self.history = History { filter, page, total: 0, entries: Vec::new() };
+                self.menus[idx] = build_history_menu_error(&e.to_string());
+            }
+        }
+
+        let len = self.menus[idx].table_mut().rows_mut().len();
+        if self.selected_rows[idx] >= len {
+            self.selected_rows[idx] = len.saturating_sub(1);
+        }
+        self.current_menu = MenuIndex::HistoryMenu;
+        self.apply_selection_marker();
+    }
+
+    pub fn history_entry(&self, row: usize) -> Option<&HistoryEntry> {
+        self.history.entries.get(row)
+    }
+
+    pub fn open_category(&mut self, category: OrderCategory) {
+        self.current_category = category;
+
+        let idx = MenuIndex::CategoryMenu.as_index();
+        self.menus[idx] = build_category_menu(
+            category,
+            &self.prebuilt_pizzas,
+            self.prebuilt_error.as_deref(),
+            &self.catalog_items,
+            &self.bundles,
+            self.active_pricing_rule.as_ref(),
+            self.dietary_filter,
+        );
+        self.selected_rows[idx] = 0;
+        self.current_menu = MenuIndex::CategoryMenu;
+        self.apply_selection_marker();
+    }
+
+    pub fn category_menu_entry(&self, row: usize) -> Option<OrderMenuEntry> {
+        match self.current_category {
+            OrderCategory::Items(Category::Pizza) => {
+                // ohne (passende) Prebuilt-Pizzen belegt der Hinweis die erste Zeile
+                let visible = self.visible_prebuilts();
+                let pizza_rows = visible.len().max(1);
+
+                if let Some(&index) = visible.get(row) {
+                    Some(OrderMenuEntry::Prebuilt(index))
+                } else if row == pizza_rows {
+                    Some(OrderMenuEntry::Custom)
+                } else {
+                    None
+                }
+            }
+            OrderCategory::Items(category) => self.catalog_items.iter()
+                .enumerate()
+                .filter(|(_, item)| item.category == category)
+                .nth(row)
+                .map(|(index, _)| OrderMenuEntry::Item(index)),
+            OrderCategory::Deals => (row < self.bundles.len()).then_some(OrderMenuEntry::Bundle(row)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderCategory {
+    Items(Category),
+    Deals,
+}
+
+impl OrderCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderCategory::Items(category) => category.label(),
+            OrderCategory::Deals => "Deals",
+        }
+    }
+}
+
+pub enum OrderMenuEntry {
+    Prebuilt(usize),
+    Item(usize),
+    Bundle(usize),
+    Custom,
+}
+
+pub enum CartMenuEntry {
+    Entry(usize),
+    Checkout,
+    Note,
+    Clear,
+    Back,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MenuIndex {
     MainMenu,
     OrderMenu,
     EditToppingsMenu,
+    CategoryMenu,
+    CartMenu,
+    HistoryMenu,
 }
 
 impl MenuIndex {
@@ -82,44 +348,39 @@ impl MenuIndex {
             MenuIndex::MainMenu => 0,
             MenuIndex::OrderMenu => 1,
             MenuIndex::EditToppingsMenu => 2,
+            MenuIndex::CategoryMenu => 3,
+            MenuIndex::CartMenu => 4,
+            MenuIndex::HistoryMenu => 5,
         }
     }
 }
 
 pub fn create_initial_state(arguments: &Arguments) -> State {
-    let toppings_catalog = load_toppings_from_backend(arguments).unwrap_or_default();
-
-    let (prebuilt_pizzas, order_menu) = match load_prebuilt_pizzas_from_backend(&toppings_catalog, arguments) {
-        Ok(pz) => {
-            let menu = build_order_menu(&pz);
-            (pz, menu)
-        }
-        Err(e) => {
-            let menu = build_order_menu_error(&e.to_string());
-            (Vec::new(), menu)
-        }
-    };
-
-    State {
+    let mut state = State {
         menus: [
             TableMenu::new(String::from("Welcome to Salvatores Pizza!"), Table::new(vec! [
                 TableRow::new( vec! [
                     TableCell::new(String::from(">")),
                     TableCell::new(String::from("1:")),
-                    TableCell::new(String::from("Order Pizza"))
+                    TableCell::new(String::from("Order"))
                 ]),
                 TableRow::new( vec! [
                     TableCell::new(String::from(" ")),
                     TableCell::new(String::from("2:")),
-                    TableCell::new(String::from("Edit Toppings"))
+                    TableCell::new(String::from("Order History"))
                 ]),
                 TableRow::new( vec! [
                     TableCell::new(String::from(" ")),
                     TableCell::new(String::from("3:")),
+                    TableCell::new(String::from("Edit Toppings"))
+                ]),
+                TableRow::new( vec! [
+                    TableCell::new(String::from(" ")),
+                    TableCell::new(String::from("4:")),
                     TableCell::new(String::from("Quit"))
                 ])
             ])),
-            order_menu,
+            build_order_menu(&[], None, &Cart::default(), None),
             TableMenu::new(String::from("Edit Toppings Menu"), Table::new(vec! [
                 TableRow::new( vec! [
                     TableCell::new(String::from(">")),
@@ -140,15 +401,42 @@ pub fn create_initial_state(arguments: &Arguments) -> State {
                     TableCell::new(String::from(" ")),
                     TableCell::new(String::from("D:")),
                     TableCell::new(String::from("Delete-List"))
+                ]),
+                TableRow::new( vec! [
+                    TableCell::new(String::from(" ")),
+                    TableCell::new(String::from("S:")),
+                    TableCell::new(String::from("Restock Topping"))
                 ])
             ])),
+            TableMenu::new(String::from("Pizza"), Table::new(vec![])),
+            build_cart_menu(&Cart::default()),
+            build_history_menu(&History::default()),
         ],
         current_menu: MenuIndex::MainMenu,
-        selected_rows: [0, 0, 0],
-        toppings_catalog,
-        prebuilt_pizzas,
+        selected_rows: [0, 0, 0, 0, 0, 0],
+        toppings_catalog: Vec::new(),
+        topping_limits: ToppingLimits::default(),
+        prebuilt_pizzas: Vec::new(),
+        prebuilt_error: None,
+        catalog_items: Vec::new(),
+        bundles: Vec::new(),
+        order_categories: Vec::new(),
+        current_category: OrderCategory::Items(Category::Pizza),
+        dietary_filter: None,
+        active_pricing_rule: None,
+        cart: Cart::default(),
+        delivery_settings: DeliverySettings::default(),
+        history: History::default(),
+        inventory_alerts: Vec::new(),
+        load_errors: Vec::new(),
         pending_fallbacks: Vec::new(),
-    }
+        pending_stock_consumes: Vec::new(),
+    };
+
+    label_hotkeys(state.menus[MenuIndex::MainMenu.as_index()].table_mut(), &[]);
+    label_hotkeys(state.menus[MenuIndex::EditToppingsMenu.as_index()].table_mut(), &[]);
+    state.refresh_order_menu(arguments);
+    state
 }
 
 pub fn load_toppings_from_backend(arguments: &Arguments) -> io::Result<Vec<Topping>> {
@@ -157,12 +445,60 @@ pub fn load_toppings_from_backend(arguments: &Arguments) -> io::Result<Vec<Toppi
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
+pub fn load_topping_limits_from_backend(arguments: &Arguments) -> io::Result<ToppingLimits> {
+    let body = get("/toppings/limits", arguments)?;
+    parse_topping_limits(&body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn load_stock_from_backend(arguments: &Arguments) -> io::Result<Vec<(String, u32)>> {
+    let body = get("/stock", arguments)?;
+    parse_stock(&body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn load_inventory_alerts_from_backend(arguments: &Arguments) -> io::Result<Vec<InventoryAlert>> {
+    let body = get("/inventory/alerts", arguments)?;
+    parse_inventory_alerts(&body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
 pub fn load_prebuilt_pizzas_from_backend(available: &[Topping], arguments: &Arguments) -> io::Result<Vec<Pizza>> {
     let body = read_pizza_prebuilds(arguments)?;
     parse_prebuild_pizza(&body, available)
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
+pub fn load_catalog_items_from_backend(arguments: &Arguments) -> io::Result<Vec<CatalogItem>> {
+    let body = get("/items", arguments)?;
+    parse_catalog_items(&body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn load_bundles_from_backend(prebuilts: &[Pizza], items: &[CatalogItem], arguments: &Arguments) -> io::Result<Vec<Bundle>> {
+    let body = get("/bundles", arguments)?;
+    parse_bundles(&body, prebuilts, items)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn load_active_pricing_rule_from_backend(arguments: &Arguments) -> io::Result<Option<PricingRule>> {
+    let body = get("/pricing", arguments)?;
+    parse_active_pricing_rule(&body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn load_delivery_settings_from_backend(arguments: &Arguments) -> io::Result<DeliverySettings> {
+    let body = get("/delivery", arguments)?;
+    parse_delivery_settings(&body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn load_history_from_backend(filter: &HistoryFilter, page: usize, arguments: &Arguments) -> io::Result<(usize, Vec<HistoryEntry>)> {
+    let body = get(&filter.request_path(page), arguments)?;
+    parse_history_body(&body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
 pub fn load_toppings_from_file(path: &str) -> io::Result<Vec<Topping>> {
     let content = fs::read_to_string(path)?;
     parse_toppings(&content)
@@ -175,55 +511,262 @@ pub fn load_prebuilt_pizzas_from_file(path: &str, available:  &[Topping]) -> io:
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
-pub fn build_order_menu(prebuilt: &[Pizza]) -> TableMenu {
+//Pizza ist immer bestellbar (Custom Pizza), übrige Kategorien nur mit Artikeln
+pub fn available_order_categories(items: &[CatalogItem], bundles: &[Bundle]) -> Vec<OrderCategory> {
+    let mut categories: Vec<OrderCategory> = Category::ALL.iter()
+        .filter(|category| **category == Category::Pizza || items.iter().any(|item| item.category == **category))
+        .map(|category| OrderCategory::Items(*category))
+        .collect();
+    if !bundles.is_empty() {
+        categories.push(OrderCategory::Deals);
+    }
+    categories
+}
+
+fn title_with_rule(title: &str, rule: Option<&PricingRule>) -> String {
+    match rule {
+        Some(rule) => format!("{title} ({})", rule.label()),
+        None => String::from(title),
+    }
+}
+
+pub fn visible_prebuilts(prebuilt: &[Pizza], filter: Option<DietaryFlag>) -> Vec<usize> {
+    prebuilt.iter()
+        .enumerate()
+        .filter(|(_, pizza)| filter.is_none_or(|flag| pizza.food_info().has(flag)))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn title_with_filter(title: String, filter: Option<DietaryFlag>) -> String {
+    match filter {
+        Some(flag) => format!("{title} [{}]", flag.label()),
+        None => title,
+    }
+}
+
+//Tasten, die order_menu_update selbst belegt
+pub const ORDER_MENU_COMMANDS: &[char] = &['v'];
+
+pub fn build_order_menu(categories: &[OrderCategory], rule: Option<&PricingRule>, cart: &Cart, filter: Option<DietaryFlag>) -> TableMenu {
     let mut table = Table::new(vec![]);
 
-    if prebuilt.is_empty() {
+    for (i, category) in categories.iter().enumerate() {
         table.push(TableRow::new(vec![
             TableCell::new(" ".into()),
-            TableCell::new("-".into()),
-            TableCell::new("Keine Prebuilt-Pizzen vorhanden".into()),
+            TableCell::new(format!("{}:", i + 1)),
+            TableCell::new(category.label().into()),
+            TableCell::new(String::new()),
         ]));
-    } else {
-        for (i, p) in prebuilt.iter().enumerate() {
+    }
+
+    // Letzte Zeile: Warenkorb
+    table.push(TableRow::new(vec![
+        TableCell::new(" ".into()),
+        TableCell::new("W:".into()),
+        TableCell::new(format!("Cart ({})", cart.item_count())),
+        TableCell::new_with_alignment(format_price(cart.total_cents()), Right),
+    ]));
+
+    label_hotkeys(&mut table, ORDER_MENU_COMMANDS);
+    TableMenu::new(title_with_filter(title_with_rule("Order Menu", rule), filter), table)
+}
+
+pub fn build_category_menu(
+    category: OrderCategory,
+    prebuilt: &[Pizza],
+    prebuilt_error: Option<&str>,
+    items: &[CatalogItem],
+    bundles: &[Bundle],
+    rule: Option<&PricingRule>,
+    filter: Option<DietaryFlag>,
+) -> TableMenu {
+    let mut table = Table::new(vec![]);
+
+    match category {
+        OrderCategory::Items(Category::Pizza) => {
+            let visible = visible_prebuilts(prebuilt, filter);
+            if let Some(error) = prebuilt_error {
+                // eine Hinweiszeile wie ohne Pizzen, Custom Pizza bleibt bestellbar
+                table.push(TableRow::new(vec![
+                    TableCell::new(" ".into()),
+                    TableCell::new("!".into()),
+                    TableCell::new(format!("Fehler beim Laden der Prebuilt-Pizzen: {error}")).with_style(theme().error),
+                ]));
+            } else if prebuilt.is_empty() {
+                table.push(TableRow::new(vec![
+                    TableCell::new(" ".into()),
+                    TableCell::new("-".into()),
+                    TableCell::new("Keine Prebuilt-Pizzen vorhanden".into()),
+                ]));
+            } else if visible.is_empty() {
+                table.push(TableRow::new(vec![
+                    TableCell::new(" ".into()),
+                    TableCell::new("-".into()),
+                    TableCell::new("Keine passenden Prebuilt-Pizzen".into()),
+                ]));
+            } else {
+                for (i, p) in visible.iter().map(|&index| &prebuilt[index]).enumerate() {
+                    let info = p.food_info();
+                    table.push(TableRow::new(vec![
+                        TableCell::new(" ".into()),
+                        TableCell::new(format!("{}:", i + 1)),
+                        TableCell::new(p.name.to_string()),
+                        TableCell::new_with_alignment(
+                            format_price(discounted_cents(p.total_price() * 100, PricingScope::Prebuilt, rule)),
+                            Right
+                        ),
+                        TableCell::new(format!(" {}", info.diet_label())),
+                        TableCell::new(info.allergen_label()),
+                        TableCell::new(if p.is_sold_out() { "sold out" } else { "" }.into()),
+                    ]).with_style(if p.is_sold_out() { theme().disabled } else { Style::plain() }));
+                }
+            }
+
+            // Letzte Zeile: Custom Pizza
             table.push(TableRow::new(vec![
                 TableCell::new(" ".into()),
-                TableCell::new(format!("{}:", i + 1)),
-                TableCell::new(p.name.to_string()),
-                TableCell::new_with_alignment(format!("{}.00$", p.total_price()), Right),
+                TableCell::new("C:".into()),
+                TableCell::new("Custom Pizza".into()),
             ]));
         }
+        OrderCategory::Items(category) => {
+            for (i, item) in items.iter().filter(|item| item.category == category).enumerate() {
+                table.push(TableRow::new(vec![
+                    TableCell::new(" ".into()),
+                    TableCell::new(format!("{}:", i + 1)),
+                    TableCell::new(item.name.to_string()),
+                    TableCell::new_with_alignment(
+                        format_price(discounted_cents(item.price * 100, PricingScope::Item, rule)),
+                        Right
+                    ),
+                ]));
+            }
+        }
+        OrderCategory::Deals => {
+            for (i, b) in bundles.iter().enumerate() {
+                table.push(TableRow::new(vec![
+                    TableCell::new(" ".into()),
+                    TableCell::new(format!("B{}:", i + 1)),
+                    TableCell::new(b.name.to_string()),
+                    TableCell::new_with_alignment(format_price(b.price * 100), Right),
+                ]));
+            }
+        }
     }
 
-    // Letzte Zeile: Custom Pizza
+    label_hotkeys(&mut table, &[]);
+
+    // Ernährungsfilter betrifft nur Pizzen
+    let filter = filter.filter(|_| category == OrderCategory::Items(Category::Pizza));
+    TableMenu::new(title_with_filter(title_with_rule(category.label(), rule), filter), table)
+}
+
+//Befehlstasten im Warenkorb (Menge, entfernen, Notiz) bekommen keine Zeile
+pub const CART_MENU_COMMANDS: &[char] = &['+', '-', 'x', 'n'];
+
+//feste Tasten der Aktionen, damit sie sich nicht mit dem Inhalt des Warenkorbs verschieben
+const CART_ACTION_KEYS: [char; 4] = ['C', 'O', 'L', 'B'];
+
+pub fn build_cart_menu(cart: &Cart) -> TableMenu {
+    let mut table = Table::new(vec![]);
+
+    if cart.is_empty() {
+        table.push(TableRow::new(vec![
+            TableCell::new(" ".into()),
+            TableCell::new("-".into()),
+            TableCell::new("Warenkorb ist leer".into()),
+            TableCell::new(String::new()),
+        ]));
+    }
+    for entry in &cart.entries {
+        table.push(TableRow::new(vec![
+            TableCell::new(" ".into()),
+            TableCell::new(":".into()),
+            TableCell::new(entry.display_name()),
+            TableCell::new_with_alignment(format_price(entry.total_cents()), Right),
+        ]));
+    }
+    let reserved: Vec<char> = CART_MENU_COMMANDS.iter().chain(&CART_ACTION_KEYS).copied().collect();
+    label_hotkeys(&mut table, &reserved);
+
     table.push(TableRow::new(vec![
         TableCell::new(" ".into()),
-        TableCell::new("C:".into()),
-        TableCell::new("Custom Pizza".into()),
+        TableCell::new(String::new()),
+        TableCell::new(String::new()),
+        TableCell::new(String::new()),
     ]));
 
-    TableMenu::new("Order Menu".into(), table)
+    // Aktionen
+    let note_label = match &cart.note {
+        Some(note) => format!("Order note: {note}"),
+        None => String::from("Order note"),
+    };
+    let actions = [
+        ("Checkout", format_price(cart.total_cents())),
+        (note_label.as_str(), String::new()),
+        ("Clear cart", String::new()),
+        ("Back", String::new()),
+    ];
+    for (key, (label, price)) in CART_ACTION_KEYS.iter().zip(actions) {
+        table.push(TableRow::new(vec![
+            TableCell::new(" ".into()),
+            TableCell::new(format!("{key}:")),
+            TableCell::new(label.into()),
+            TableCell::new_with_alignment(price, Right),
+        ]));
+    }
+
+    TableMenu::new(String::from("Cart"), table)
+}
+
+//lange Sammelbestellungen kürzen, Details zeigt die Detailansicht
+const HISTORY_NAME_WIDTH: usize = 40;
+
+pub fn build_history_menu(history: &History) -> TableMenu {
+    let mut table = Table::new(vec![]);
+
+    if history.entries.is_empty() {
+        table.push(TableRow::new(vec![
+            TableCell::new(" ".into()),
+            TableCell::new("-".into()),
+            TableCell::new(String::new()),
+            TableCell::new("Keine Bestellungen gefunden".into()),
+            TableCell::new(String::new()),
+        ]));
+    }
+    for entry in &history.entries {
+        let name = entry.list_name(HISTORY_NAME_WIDTH);
+        table.push(TableRow::new(vec![
+            TableCell::new(" ".into()),
+            TableCell::new_with_alignment(format!("#{}", entry.number), Right),
+            TableCell::new(format!(" {}", entry.timestamp)),
+            TableCell::new(name),
+            TableCell::new_with_alignment(entry.price_text(), Right),
+        ]));
+    }
+
+    let mut title = format!("Order History (Seite {}/{})", history.page.max(1), history.page_count());
+    if !history.filter.is_empty() {
+        title.push_str(&format!(" - {}", history.filter.label()));
+    }
+    TableMenu::new(title, table)
 }
 
-pub fn build_order_menu_error(err_msg: &str) -> TableMenu {
+pub fn build_history_menu_error(err_msg: &str) -> TableMenu {
     let table = Table::new(vec![
         TableRow::new(vec![
             TableCell::new(" ".into()),
             TableCell::new("! ".into()),
-            TableCell::new(String::from("Fehler beim Laden der Prebuilt-Pizzen:")),
+            TableCell::new(String::from("Fehler beim Laden der Bestellhistorie:")),
         ]),
         TableRow::new(vec![
             TableCell::new(" ".into()),
             TableCell::new(" ".into()),
             TableCell::new(String::from(err_msg)),
         ]),
-        TableRow::new(vec![
-            TableCell::new(" ".into()),
-            TableCell::new("=>".into()),
-            TableCell::new("Bitte 'pizza_prebuilds_text' korrigieren.".into()),
-        ]),
     ]);
-    TableMenu::new("Order Menu (Fehler)".into(), table)
+    TableMenu::new("Order History (Fehler)".into(), table)
 }
 
 #[derive(Clone)]
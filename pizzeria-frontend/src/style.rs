@@ -0,0 +1,304 @@
+//Textattribute und Farben für Tabellenzellen und Meldungen; das Theme ordnet
+//Rollen (Fehler, Erfolg, ausgewählte Zeile, …) einen Stil zu
+use std::sync::OnceLock;
+use std::{env, fs};
+use crate::error::FrontendError;
+
+//Pfad zu einer Theme-Datei (Format siehe parse_theme), sonst gilt Theme::default()
+pub const THEME_PATH_KEY: &str = "PIZZERIA_FRONTEND_THEME";
+//https://no-color.org: gesetzt und nicht leer = keine Farben, Attribute wie fett bleiben
+pub const NO_COLOR_KEY: &str = "NO_COLOR";
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn parse(text: &str) -> Option<Color> {
+        Some(match text {
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "white" => Color::White,
+            _ => return None,
+        })
+    }
+
+    fn code(&self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Style {
+    pub bold: bool,
+    pub dim: bool,
+    pub underline: bool,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Style {
+    pub const fn plain() -> Style {
+        Style { bold: false, dim: false, underline: false, fg: None, bg: None }
+    }
+
+    pub const fn bold(self) -> Style {
+        Style { bold: true, ..self }
+    }
+
+    pub const fn dim(self) -> Style {
+        Style { dim: true, ..self }
+    }
+
+    pub const fn underline(self) -> Style {
+        Style { underline: true, ..self }
+    }
+
+    pub const fn fg(self, color: Color) -> Style {
+        Style { fg: Some(color), ..self }
+    }
+
+    pub const fn bg(self, color: Color) -> Style {
+        Style { bg: Some(color), ..self }
+    }
+
+    //eigene Angaben haben Vorrang, fehlende kommen aus `other` (z.B. Zelle in ausgewählter Zeile)
+    pub fn merge(self, other: Style) -> Style {
+        Style {
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            underline: self.underline || other.underline,
+            fg: self.fg.or(other.fg),
+            bg: self.bg.or(other.bg),
+        }
+    }
+
+    fn without_colors(self) -> Style {
+        Style { fg: None, bg: None, ..self }
+    }
+
+    fn codes(&self) -> Vec<String> {
+        let mut codes = Vec::new();
+        if self.bold { codes.push(String::from("1")); }
+        if self.dim { codes.push(String::from("2")); }
+        if self.underline { codes.push(String::from("4")); }
+        codes.extend(self.fg.map(|color| (30 + color.code()).to_string()));
+        codes.extend(self.bg.map(|color| (40 + color.code()).to_string()));
+        codes
+    }
+
+    pub fn paint(&self, text: &str) -> String {
+        let codes = self.codes();
+        if codes.is_empty() || text.is_empty() {
+            return text.to_string();
+        }
+        format!("\x1b[{}m{text}\x1b[0m", codes.join(";"))
+    }
+
+    // Format: bold|dim|underline|fg=<Farbe>|bg=<Farbe>, leer = ohne Stil
+    fn parse(text: &str) -> Result<Style, String> {
+        let mut style = Style::plain();
+        for part in text.split('|').map(str::trim).filter(|part| !part.is_empty()) {
+            style = match part.split_once('=') {
+                Some(("fg", color)) => style.fg(Color::parse(color.trim()).ok_or_else(|| format!("Unbekannte Farbe '{}'", color.trim()))?),
+                Some(("bg", color)) => style.bg(Color::parse(color.trim()).ok_or_else(|| format!("Unbekannte Farbe '{}'", color.trim()))?),
+                None if part == "bold" => style.bold(),
+                None if part == "dim" => style.dim(),
+                None if part == "underline" => style.underline(),
+                _ => return Err(format!("Unbekanntes Attribut '{part}'")),
+            };
+        }
+        Ok(style)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Theme {
+    //Menütitel
+    pub title: Style,
+    //Überschriften der Eingabedialoge, z.B. "Topping hinzufügen"
+    pub heading: Style,
+    //Zeile mit dem Auswahlmarker
+    pub selected: Style,
+    //nicht wählbare Zeilen, z.B. ausverkaufte Pizzen
+    pub disabled: Style,
+    pub error: Style,
+    pub success: Style,
+    pub warning: Style,
+    pub notice: Style,
+    //Namen und Beträge in Meldungen
+    pub emphasis: Style,
+    pub price: Style,
+    //Feldnamen bei Eingaben
+    pub prompt: Style,
+    //Rückfragen und ihre Antworten (Y/N)
+    pub question: Style,
+    pub yes: Style,
+    pub no: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            title: Style::plain(),
+            heading: Style::plain().bold().fg(Color::Red),
+            selected: Style::plain(),
+            disabled: Style::plain().dim(),
+            error: Style::plain().bold().fg(Color::Red),
+            success: Style::plain().underline().fg(Color::Green),
+            warning: Style::plain().bold().fg(Color::Yellow),
+            notice: Style::plain().bold().fg(Color::Magenta),
+            emphasis: Style::plain().bold(),
+            price: Style::plain().underline().fg(Color::Black),
+            prompt: Style::plain().underline().fg(Color::Blue),
+            question: Style::plain().fg(Color::Blue),
+            yes: Style::plain().fg(Color::Green),
+            no: Style::plain().fg(Color::Red),
+        }
+    }
+}
+
+impl Theme {
+    fn role_mut(&mut self, role: &str) -> Option<&mut Style> {
+        Some(match role {
+            "title" => &mut self.title,
+            "heading" => &mut self.heading,
+            "selected" => &mut self.selected,
+            "disabled" => &mut self.disabled,
+            "error" => &mut self.error,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "notice" => &mut self.notice,
+            "emphasis" => &mut self.emphasis,
+            "price" => &mut self.price,
+            "prompt" => &mut self.prompt,
+            "question" => &mut self.question,
+            "yes" => &mut self.yes,
+            "no" => &mut self.no,
+            _ => return None,
+        })
+    }
+
+    fn without_colors(self) -> Theme {
+        Theme {
+            title: self.title.without_colors(),
+            heading: self.heading.without_colors(),
+            selected: self.selected.without_colors(),
+            disabled: self.disabled.without_colors(),
+            error: self.error.without_colors(),
+            success: self.success.without_colors(),
+            warning: self.warning.without_colors(),
+            notice: self.notice.without_colors(),
+            emphasis: self.emphasis.without_colors(),
+            price: self.price.without_colors(),
+            prompt: self.prompt.without_colors(),
+            question: self.question.without_colors(),
+            yes: self.yes.without_colors(),
+            no: self.no.without_colors(),
+        }
+    }
+
+    //"Frage? (Y/N): " in den Farben des Themes
+    pub fn confirm_question(&self, question: &str) -> String {
+        format!("\n{} ({}/{}): ", self.question.paint(question), self.yes.paint("Y"), self.no.paint("N"))
+    }
+}
+
+// Format: <Rolle>#<Attribute> je Zeile, z.B. "selected#bold|fg=black|bg=yellow";
+// nicht genannte Rollen behalten den Standardstil
+pub fn parse_theme(content: &str) -> Result<Theme, String> {
+    let mut theme = Theme::default();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (role, attributes) = line.split_once('#')
+            .ok_or_else(|| format!("Zeile {}: erwartet 'Rolle#Attribute'", lineno + 1))?;
+        let style = theme.role_mut(role.trim())
+            .ok_or_else(|| format!("Zeile {}: Unbekannte Rolle '{}'", lineno + 1, role.trim()))?;
+        *style = Style::parse(attributes).map_err(|error| format!("Zeile {}: {error}", lineno + 1))?;
+    }
+
+    Ok(theme)
+}
+
+fn colors_disabled() -> bool {
+    env::var_os(NO_COLOR_KEY).is_some_and(|value| !value.is_empty())
+}
+
+fn load_theme() -> Result<Theme, FrontendError> {
+    let theme = match env::var_os(THEME_PATH_KEY).filter(|path| !path.is_empty()) {
+        Some(path) => {
+            let path = path.to_string_lossy().into_owned();
+            let content = fs::read_to_string(&path)
+                .map_err(|error| FrontendError::Theme { path: path.clone(), message: error.to_string() })?;
+            parse_theme(&content).map_err(|message| FrontendError::Theme { path, message })?
+        }
+        None => Theme::default(),
+    };
+    Ok(if colors_disabled() { theme.without_colors() } else { theme })
+}
+
+//beim Start aufrufen, damit Fehler in der Theme-Datei vor dem Raw-Modus gemeldet werden
+pub fn init_theme() -> Result<(), FrontendError> {
+    let theme = load_theme()?;
+    THEME.get_or_init(|| theme);
+    Ok(())
+}
+
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(|| load_theme().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint_styles() {
+        assert_eq!(Style::plain().bold().fg(Color::Red).paint("Fehler"), "\x1b[1;31mFehler\x1b[0m");
+        assert_eq!(Style::plain().paint("Ham"), "Ham");
+        assert_eq!(Theme::default().without_colors().error.paint("Fehler"), "\x1b[1mFehler\x1b[0m");
+        assert_eq!(Style::plain().fg(Color::Cyan).merge(Style::plain().bold().fg(Color::Black).bg(Color::Yellow)),
+                   Style::plain().bold().fg(Color::Cyan).bg(Color::Yellow));
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let theme = parse_theme("selected#bold|fg=black|bg=yellow\n\ndisabled#\nheading#underline\n").unwrap();
+
+        assert_eq!(theme.selected, Style::plain().bold().fg(Color::Black).bg(Color::Yellow));
+        assert_eq!(theme.disabled, Style::plain());
+        assert_eq!(theme.heading, Style::plain().underline());
+        assert_eq!(theme.error, Theme::default().error);
+        assert!(parse_theme("selected#blink").is_err());
+        assert!(parse_theme("selected#fg=orange").is_err());
+        assert!(parse_theme("cursor#bold").is_err());
+    }
+}
@@ -1,3 +1,6 @@
+use crate::style::Style;
+use crate::width::display_width;
+
 pub struct Table {
     rows: Vec<TableRow>,
 }
@@ -18,6 +21,26 @@ impl Table {
     pub fn is_empty(&self) -> bool {
         self.rows.is_empty()
     }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn cell_text(&self, row: usize, column: usize) -> Option<&str> {
+        self.rows.get(row)?.cells.get(column).map(|cell| cell.text.as_str())
+    }
+
+    //Text einer Zeile ohne Auswahlmarker, für die Suche im Menü
+    pub fn row_text(&self, index: usize) -> String {
+        self.rows.get(index)
+            .map(|row| row.cells.iter().skip(1).map(|cell| cell.text.trim()).collect::<Vec<_>>().join(" "))
+            .unwrap_or_default()
+    }
+
+    //Zeile mit dem Auswahlmarker '>' in der ersten Zelle (siehe update::select_row)
+    pub fn marked_row(&self) -> Option<usize> {
+        self.rows.iter().position(|row| row.cells.first().is_some_and(|cell| cell.text == ">"))
+    }
 }
 
 pub struct TableRow {
@@ -32,19 +55,33 @@ impl TableRow {
     pub fn cells_mut(&mut self) -> &mut[TableCell] {
         self.cells.as_mut_slice()
     }
+
+    //Stil für alle Zellen, die noch keinen eigenen haben (z.B. ausgegraute Zeile)
+    pub fn with_style(mut self, style: Style) -> Self {
+        for cell in &mut self.cells {
+            cell.style = cell.style.merge(style);
+        }
+        self
+    }
 }
 
 pub struct TableCell {
     text: String,
-    align: Align
+    align: Align,
+    style: Style,
 }
 
 impl TableCell {
     pub fn new(text: String) -> Self {
-        Self { text, align: Align::Left }
+        Self { text, align: Align::Left, style: Style::plain() }
     }
     pub fn new_with_alignment(text: String, align: Align) -> Self {
-        Self { text, align }
+        Self { text, align, style: Style::plain() }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
     }
 
     pub fn text_mut(&mut self) -> &mut String {
@@ -58,11 +95,20 @@ pub enum Align {
 }
 
 pub fn render_table(table: &Table, buffer: &mut String) {
+    let rows: Vec<usize> = (0..table.rows.len()).collect();
+    render_table_rows(table, &rows, Style::plain(), buffer);
+}
+
+//nur die angegebenen Zeilen ausgeben; Spaltenbreiten gelten für die ganze Tabelle,
+//damit sich das Layout beim Scrollen und Filtern nicht verschiebt;
+//selected_style gilt zusätzlich für die Zeile mit dem Auswahlmarker
+pub fn render_table_rows(table: &Table, rows: &[usize], selected_style: Style, buffer: &mut String) {
+    let marked = table.marked_row();
 
     let mut columns_width = Vec::<usize>::new();
     for row in table.rows.iter() {
         for (index, column) in row.cells.iter().enumerate() {
-            let text_length = column.text.len();
+            let text_length = display_width(&column.text);
             if let Some(entry) = columns_width.get_mut(index) {
                 if *entry < text_length {
                     *entry = text_length;
@@ -73,22 +119,25 @@ pub fn render_table(table: &Table, buffer: &mut String) {
         }
     }
 
-    for row in table.rows.iter() {
+    for (row_index, row) in rows.iter().filter_map(|index| Some((*index, table.rows.get(*index)?))) {
+        let row_style = if Some(row_index) == marked { selected_style } else { Style::plain() };
         for (index, column) in row.cells.iter().enumerate() {
+            let text = column.style.merge(row_style).paint(&column.text);
             let column_width = columns_width[index];
+            // Abstände mitfärben, damit die ausgewählte Zeile durchgehend hinterlegt ist
             let padding = if index < row.cells.len() - 1 {
-                column_width - column.text.len() + 2
+                column_width - display_width(&column.text) + 2
             } else {
-                column_width - column.text.len()
+                column_width - display_width(&column.text)
             };
             match column.align {
                 Align::Left => {
-                    buffer.push_str(column.text.as_str());
-                    buffer.push_str(" ".repeat(padding).as_str());
+                    buffer.push_str(text.as_str());
+                    buffer.push_str(row_style.paint(&" ".repeat(padding)).as_str());
                 }
                 Align::Right => {
-                    buffer.push_str(" ".repeat(padding).as_str());
-                    buffer.push_str(column.text.as_str());
+                    buffer.push_str(row_style.paint(&" ".repeat(padding)).as_str());
+                    buffer.push_str(text.as_str());
                 }
             }
 
@@ -108,16 +157,16 @@ mod tests {
             rows: vec![
                 TableRow {
                     cells: vec![
-                        TableCell { text: String::from("1."), align: Align::Left },
-                        TableCell { text: String::from("Mushrooms"), align: Align::Left },
-                        TableCell { text: String::from("3.00$"), align: Align::Right },
+                        TableCell::new(String::from("1.")),
+                        TableCell::new_with_alignment(String::from("Mushrooms"), Align::Left),
+                        TableCell::new_with_alignment(String::from("3.00$"), Align::Right),
                     ]
                 },
                 TableRow {
                     cells: vec![
-                        TableCell { text: String::from("2."), align: Align::Left },
-                        TableCell { text: String::from("Onions"), align: Align::Left },
-                        TableCell { text: String::from("22.00$"), align: Align::Right },
+                        TableCell::new_with_alignment(String::from("2."), Align::Left),
+                        TableCell::new_with_alignment(String::from("Onions"), Align::Left),
+                        TableCell::new_with_alignment(String::from("22.00$"), Align::Right),
                     ]
                 },
             ]
@@ -1,29 +1,169 @@
 use std::cmp::max;
 use std::fmt::{Display, Formatter};
-use crate::table::{render_table, Table};
+use std::ops::Range;
+use crate::filter::fuzzy_match;
+use crate::style::{theme, Style};
+use crate::table::{render_table_rows, Table};
+use crate::width::{display_width, pad_to_width};
 
 pub struct TableMenu {
     title: String,
     table: Table,
+    //Scroll-Fenster: erste sichtbare Zeile und Anzahl sichtbarer Zeilen (None = alle Zeilen)
+    offset: usize,
+    visible: Option<usize>,
+    //Suchtext (siehe filter.rs), None = keine Suche aktiv
+    filter: Option<String>,
+    //zuletzt gezeichnete Zeilen auf dem Bildschirm, für Mausklicks (siehe render_menu)
+    screen_rows: Vec<ScreenRow>,
+    //Stil des Titels und der Zeile mit dem Auswahlmarker, Standard aus dem Theme
+    title_style: Style,
+    selected_style: Style,
+}
+
+//Bildschirmzeile (ab 1) und Spaltenbereich einer gezeichneten Tabellenzeile
+pub struct ScreenRow {
+    pub line: usize,
+    pub columns: Range<usize>,
+    pub row: usize,
 }
 
 impl TableMenu {
     pub fn new(title: String, table: Table) -> Self {
-        Self { title, table }
+        Self {
+            title, table, offset: 0, visible: None, filter: None, screen_rows: Vec::new(),
+            title_style: theme().title, selected_style: theme().selected,
+        }
+    }
+
+    pub fn with_styles(mut self, title_style: Style, selected_style: Style) -> Self {
+        self.title_style = title_style;
+        self.selected_style = selected_style;
+        self
     }
     
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
     pub fn table_mut(&mut self) -> &mut Table {
         &mut self.table
     }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    //Scroll-Position eines neu aufgebauten Menüs übernehmen (z.B. im Custom-Pizza-Dialog)
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    pub fn set_filter(&mut self, filter: Option<String>) {
+        self.filter = filter;
+    }
+
+    //Indizes der Zeilen, die zum Suchtext passen (ohne Suche alle)
+    pub fn visible_rows(&self) -> Vec<usize> {
+        (0..self.table.len())
+            .filter(|index| match &self.filter {
+                Some(query) => fuzzy_match(query, &self.table.row_text(*index)),
+                None => true,
+            })
+            .collect()
+    }
+
+    //Bereich der sichtbaren Zeilen (Indizes in visible_rows) im Scroll-Fenster
+    fn window(&self, total: usize) -> (usize, usize) {
+        match self.visible {
+            Some(height) => (self.offset.min(total), (self.offset + height).min(total)),
+            None => (0, total),
+        }
+    }
+
+    //(Zeile innerhalb der Box, Tabellenzeile) für alle Zeilen im Fenster, wie von Display ausgegeben
+    pub fn window_lines(&self) -> Vec<(usize, usize)> {
+        let rows = self.visible_rows();
+        let (first, end) = self.window(rows.len());
+        // Rahmen, Titel, Trennlinie und ggf. der obere Scroll-Hinweis
+        let header = 3 + usize::from(self.visible.is_some());
+        rows[first..end].iter().enumerate().map(|(index, row)| (header + index, *row)).collect()
+    }
+
+    pub fn set_screen_rows(&mut self, screen_rows: Vec<ScreenRow>) {
+        self.screen_rows = screen_rows;
+    }
+
+    //Tabellenzeile an einer Klickposition (Spalte und Zeile ab 1)
+    pub fn row_at(&self, column: usize, line: usize) -> Option<usize> {
+        self.screen_rows.iter()
+            .find(|screen_row| screen_row.line == line && screen_row.columns.contains(&column))
+            .map(|screen_row| screen_row.row)
+    }
+
+    //Zeilen pro Seite für PageUp/PageDown
+    pub fn page_size(&self) -> usize {
+        self.visible.unwrap_or(self.table.len()).max(1)
+    }
+
+    //max_lines = Platz für Tabellenzeilen inkl. der beiden Scroll-Hinweise;
+    //das Fenster folgt der markierten Zeile und scrollt nur so weit wie nötig
+    pub fn scroll_into_view(&mut self, max_lines: usize) {
+        let rows = self.visible_rows();
+        let total = rows.len();
+        if total <= max_lines {
+            self.offset = 0;
+            self.visible = None;
+            return;
+        }
+
+        let height = max_lines.saturating_sub(2).max(1);
+        let marked = self.table.marked_row();
+        if let Some(row) = rows.iter().position(|row| Some(*row) == marked) {
+            if row < self.offset {
+                self.offset = row;
+            } else if row >= self.offset + height {
+                self.offset = row + 1 - height;
+            }
+        }
+        self.offset = self.offset.min(total - height);
+        self.visible = Some(height);
+    }
 }
 
 impl Display for TableMenu {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = self.visible_rows();
+        let total = rows.len();
+        let (first, end) = self.window(total);
+
         let mut table_buffer = String::new();
-        render_table(&self.table, &mut table_buffer);
+        render_table_rows(&self.table, &rows[first..end], self.selected_style, &mut table_buffer);
+        if total == 0 && self.filter.is_some() {
+            table_buffer.push_str("(keine Treffer)\n");
+        }
+
+        // Scroll-Hinweise über und unter dem sichtbaren Fenster
+        let hints = self.visible.map(|_| {
+            let above = if first > 0 { format!("^ {first} more") } else { String::new() };
+            let below = if end < total { format!("v {} more", total - end) } else { String::new() };
+            (above, below)
+        });
+
+        let hint_length = hints.as_ref()
+            .map(|(above, below)| max(display_width(above), display_width(below)))
+            .unwrap_or(0);
         let max_length = max(
-            self.title.len(),
-            table_buffer.split('\n').next().unwrap_or_default().len()
+            max(display_width(&self.title), hint_length),
+            table_buffer.split('\n').map(display_width).max().unwrap_or_default()
         );
 
         let min_width = max_length + 4;
@@ -31,23 +171,29 @@ impl Display for TableMenu {
         let bottom = format!("└{}┘", "─".repeat(min_width));
 
         let title_text = {
-            let padding = min_width - self.title.len();
+            let padding = min_width - display_width(&self.title);
             let left_padding =  padding / 2;
             let right_padding = (padding as f32 / 2_f32).ceil() as usize;
-            format!("│{}{}{}│\n", " ".repeat(left_padding), self.title, " ".repeat(right_padding))
+            format!("│{}{}{}│\n", " ".repeat(left_padding), self.title_style.paint(&self.title), " ".repeat(right_padding))
         };
         let divider = format!("├{}┤\n", "─".repeat(min_width ));
 
         writeln!(f,"{top}")?;
         write!(f, "{title_text}")?;
         write!(f, "{divider}")?;
+        if let Some((above, _)) = &hints {
+            writeln!(f, "│  {}  │", pad_to_width(above, max_length))?;
+        }
         for table_line in table_buffer.split("\n") {
             if !table_line.is_empty() {
-                let padding_size = max_length - table_line.len();
+                let padding_size = max_length - display_width(table_line);
                 let padding = " ".repeat(padding_size);
                 writeln!(f, "│  {table_line}{padding}  │" )?;
             }
         }
+        if let Some((_, below)) = &hints {
+            writeln!(f, "│  {}  │", pad_to_width(below, max_length))?;
+        }
         writeln!(f, "{bottom}")?;
 
 
@@ -157,4 +303,69 @@ mod tests {
 
     Ok(())
     }
+
+    #[test]
+    fn test_viewport_follows_marked_row() -> Result<(), Box<dyn std::error::Error>> {
+        let rows = (1..=6).map(|i| TableRow::new(vec![
+            TableCell::new(String::from(if i == 5 { ">" } else { " " })),
+            TableCell::new(format!("Topping {i}")),
+        ])).collect();
+        let mut table_menu = TableMenu::new(String::from("Toppings"), Table::new(rows));
+
+        table_menu.scroll_into_view(5);
+        assert_eq!(table_menu.offset(), 2);
+        assert_eq!(table_menu.page_size(), 3);
+        assert_eq!(table_menu.window_lines(), vec![(4, 2), (5, 3), (6, 4)]);
+        assert_eq!(format!("{table_menu}").trim(), "
+┌────────────────┐
+│    Toppings    │
+├────────────────┤
+│  ^ 2 more      │
+│     Topping 3  │
+│     Topping 4  │
+│  >  Topping 5  │
+│  v 1 more      │
+└────────────────┘
+".trim());
+
+        table_menu.scroll_into_view(6);
+        assert_eq!(table_menu.offset(), 0);
+        assert_eq!(table_menu.page_size(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_menu_with_wide_characters() -> Result<(), Box<dyn std::error::Error>> {
+        let table = Table::new(vec![
+            TableRow::new(vec![
+                TableCell::new(String::from("1.")),
+                TableCell::new(String::from("Käse")),
+                TableCell::new_with_alignment(String::from("2.00$"), Align::Right),
+            ]),
+            TableRow::new(vec![
+                TableCell::new(String::from("2.")),
+                TableCell::new(String::from("寿司")),
+                TableCell::new_with_alignment(String::from("12.00$"), Align::Right),
+            ]),
+            TableRow::new(vec![
+                TableCell::new(String::from("3.")),
+                TableCell::new(String::from("\x1b[1mHam\x1b[0m")),
+                TableCell::new_with_alignment(String::from("6.00$"), Align::Right),
+            ]),
+        ]);
+        let table_menu = TableMenu::new(String::from("Toppings 🍕"), table);
+
+        assert_eq!(format!("{table_menu}").trim(), "
+┌────────────────────┐
+│    Toppings 🍕     │
+├────────────────────┤
+│  1.  Käse   2.00$  │
+│  2.  寿司  12.00$  │
+│  3.  \x1b[1mHam\x1b[0m    6.00$  │
+└────────────────────┘
+".trim());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,262 @@
+use std::io;
+use std::panic;
+use std::ptr;
+use std::fs::File;
+use std::os::fd::IntoRawFd;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::width::{display_width, truncate_to_width};
+
+//Fallback, wenn weder TIOCGWINSZ noch LINES/COLUMNS eine Größe liefern
+pub const DEFAULT_SIZE: TerminalSize = TerminalSize { cols: 80, rows: 24 };
+
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+//alternativer Bildschirm (1049), Klicks und Mausrad melden (1000), Koordinaten im SGR-Format (1006)
+//auch jenseits von Spalte 223; beim Verlassen zusätzlich den Cursor wieder einblenden
+const ENTER_SEQUENCE: &[u8] = b"\x1b[?1049h\x1b[?1000h\x1b[?1006h";
+const LEAVE_SEQUENCE: &[u8] = b"\x1b[?1006l\x1b[?1000l\x1b[?25h\x1b[?1049l";
+
+//Einstellungen für Guard, Signal-Handler und Panic-Hook: Terminal-fd, ursprünglicher und Raw-Modus
+struct TerminalModes {
+    fd: libc::c_int,
+    original: libc::termios,
+    raw: libc::termios,
+}
+
+static MODES: OnceLock<TerminalModes> = OnceLock::new();
+//true, solange Raw-Modus und alternativer Bildschirm aktiv sind
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+//true zwischen Ctrl+Z und SIGCONT, wenn das Terminal dafür zurückgesetzt wurde
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TerminalSize {
+    pub cols: usize,
+    pub rows: usize,
+}
+
+pub fn terminal_size() -> TerminalSize {
+    unsafe {
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) == 0 && size.ws_col > 0 && size.ws_row > 0 {
+            return TerminalSize { cols: size.ws_col as usize, rows: size.ws_row as usize };
+        }
+    }
+
+    let from_env = |key: &str| std::env::var(key).ok().and_then(|value| value.parse::<usize>().ok());
+    match (from_env("COLUMNS"), from_env("LINES")) {
+        (Some(cols), Some(rows)) => TerminalSize { cols, rows },
+        _ => DEFAULT_SIZE,
+    }
+}
+
+//Raw-Modus und alternativer Bildschirm für die Dauer des Guards; das Terminal wird beim Drop,
+//bei einer Panic und für Ctrl+Z (SIGTSTP) wiederhergestellt und nach SIGCONT erneut eingerichtet
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<TerminalGuard> {
+        // ohne Terminal auf stdin (z.B. umgeleitet) direkt /dev/tty, der fd bleibt offen
+        let fd = if unsafe { libc::isatty(libc::STDIN_FILENO) } == 1 {
+            libc::STDIN_FILENO
+        } else {
+            File::open("/dev/tty")?.into_raw_fd()
+        };
+
+        let original = unsafe {
+            let mut termios = std::mem::MaybeUninit::uninit();
+            if libc::tcgetattr(fd, termios.as_mut_ptr()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            termios.assume_init()
+        };
+        let mut raw = original;
+        unsafe { libc::cfmakeraw(&mut raw) };
+        // Ausgabe-Flags behalten, damit \n weiterhin zu \r\n wird
+        raw.c_oflag = original.c_oflag;
+
+        MODES.set(TerminalModes { fd, original, raw })
+            .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "Terminal ist bereits eingerichtet"))?;
+
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // Meldung auf dem normalen Bildschirm und mit funktionierender Shell ausgeben
+            deactivate();
+            previous_hook(info);
+        }));
+        install_handler(libc::SIGTSTP, on_suspend)?;
+        install_handler(libc::SIGCONT, on_continue)?;
+
+        activate()?;
+        Ok(TerminalGuard { _private: () })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        deactivate();
+    }
+}
+
+//nur write() und tcsetattr(), damit die Funktionen auch im Signal-Handler laufen dürfen
+fn write_sequence(bytes: &[u8]) {
+    unsafe {
+        libc::write(libc::STDOUT_FILENO, bytes.as_ptr().cast(), bytes.len());
+    }
+}
+
+fn activate() -> io::Result<()> {
+    let Some(modes) = MODES.get() else { return Ok(()) };
+    if ACTIVE.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    if unsafe { libc::tcsetattr(modes.fd, libc::TCSADRAIN, &modes.raw) } != 0 {
+        ACTIVE.store(false, Ordering::SeqCst);
+        return Err(io::Error::last_os_error());
+    }
+    write_sequence(ENTER_SEQUENCE);
+    Ok(())
+}
+
+//mehrfacher Aufruf (Panic-Hook und danach Drop beim Abwickeln) ist harmlos
+fn deactivate() {
+    let Some(modes) = MODES.get() else { return };
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    write_sequence(LEAVE_SEQUENCE);
+    unsafe {
+        libc::tcsetattr(modes.fd, libc::TCSADRAIN, &modes.original);
+    }
+}
+
+//Ctrl+Z kommt im Raw-Modus als Taste an (siehe input.rs) und wird hierüber zum SIGTSTP
+pub fn suspend() {
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+}
+
+extern "C" fn on_suspend(_signal: libc::c_int) {
+    SUSPENDED.store(ACTIVE.load(Ordering::SeqCst), Ordering::SeqCst);
+    deactivate();
+    unsafe {
+        // mit der Standardaktion anhalten; SIGTSTP ist im Handler blockiert, daher freigeben
+        libc::signal(libc::SIGTSTP, libc::SIG_DFL);
+        let mut mask: libc::sigset_t = std::mem::zeroed();
+        libc::sigemptyset(&mut mask);
+        libc::sigaddset(&mut mask, libc::SIGTSTP);
+        libc::sigprocmask(libc::SIG_UNBLOCK, &mask, ptr::null_mut());
+        libc::raise(libc::SIGTSTP);
+    }
+    // weiter nach SIGCONT (on_continue ist dann schon gelaufen)
+    let _ = install_handler(libc::SIGTSTP, on_suspend);
+}
+
+extern "C" fn on_continue(_signal: libc::c_int) {
+    if SUSPENDED.swap(false, Ordering::SeqCst) {
+        let _ = activate();
+    }
+    // Bildschirm vollständig neu zeichnen, read() kehrt mit EINTR zurück
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+extern "C" fn on_resize(_signal: libc::c_int) {
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+//ohne SA_RESTART: ein blockierendes read() kehrt beim Signal mit EINTR zurück
+fn install_handler(signal: libc::c_int, handler: extern "C" fn(libc::c_int)) -> io::Result<()> {
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handler as libc::sighandler_t;
+        libc::sigemptyset(&mut action.sa_mask);
+        action.sa_flags = 0;
+        if libc::sigaction(signal, &action, ptr::null_mut()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+pub fn install_resize_handler() -> io::Result<()> {
+    install_handler(libc::SIGWINCH, on_resize)
+}
+
+//true, wenn sich die Fenstergröße seit dem letzten Aufruf geändert hat
+pub fn take_resize() -> bool {
+    RESIZED.swap(false, Ordering::SeqCst)
+}
+
+//Menü-Box an das Terminal anpassen: zu breite Zeilen kürzen (Rahmen bleibt geschlossen),
+//zu hohe Boxen unten abschneiden und die Box zentrieren; reserved = Zeilen für die Fußzeile
+pub fn fit_box(lines: &[String], size: TerminalSize, reserved: usize) -> Vec<String> {
+    let width = lines.iter().map(|line| display_width(line)).max().unwrap_or(0);
+    let max_rows = size.rows.saturating_sub(reserved).max(3);
+
+    let mut fitted: Vec<String> = lines.iter()
+        .map(|line| {
+            if display_width(line) <= size.cols || size.cols < 2 {
+                return line.clone();
+            }
+            // letztes Zeichen ist der rechte Rahmen
+            let mut cut = truncate_to_width(line, size.cols - 1);
+            cut.extend(line.chars().last());
+            cut
+        })
+        .collect();
+
+    if fitted.len() > max_rows {
+        let bottom = fitted.pop().unwrap_or_default();
+        fitted.truncate(max_rows - 1);
+        fitted.push(bottom);
+    }
+
+    let left = " ".repeat(size.cols.saturating_sub(width) / 2);
+    let top = size.rows.saturating_sub(reserved + fitted.len()) / 2;
+
+    let mut centered = vec![String::new(); top];
+    centered.extend(fitted.into_iter().map(|line| format!("{left}{line}")));
+    centered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu_box() -> Vec<String> {
+        vec![
+            String::from("┌──────────┐"),
+            String::from("│   Menu   │"),
+            String::from("├──────────┤"),
+            String::from("│  1. Ham  │"),
+            String::from("│  2. Corn │"),
+            String::from("└──────────┘"),
+        ]
+    }
+
+    #[test]
+    fn test_fit_box_centers_small_menu() {
+        let fitted = fit_box(&menu_box(), TerminalSize { cols: 20, rows: 10 }, 2);
+
+        assert_eq!(fitted.len(), 1 + 6);
+        assert_eq!(fitted[0], "");
+        assert_eq!(fitted[1], "    ┌──────────┐");
+    }
+
+    #[test]
+    fn test_fit_box_clamps_to_terminal() {
+        let fitted = fit_box(&menu_box(), TerminalSize { cols: 8, rows: 7 }, 2);
+
+        assert_eq!(fitted, vec![
+            String::from("┌──────┐"),
+            String::from("│   Men│"),
+            String::from("├──────┤"),
+            String::from("│  1. H│"),
+            String::from("└──────┘"),
+        ]);
+    }
+}
@@ -5,10 +5,11 @@ use crate::table::{Align, Table, TableCell, TableRow};
 use crate::table_menu::TableMenu;
 use crate::{clear_screen, Arguments};
 use crate::custom_toppings::list_toppings_from_backend;
+use crate::style::theme;
 
 pub fn edit_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> Result<(), Box<dyn std::error::Error>> {
 
-    writeln!(stdout, "\x1b[1;31mToppings Editor\x1b[0m <Topping-Name> <Preis>: ")?;
+    writeln!(stdout, "{} <Topping-Name> <Preis>: ", theme().heading.paint("Toppings Editor"))?;
     stdout.flush()?;
 
     let file_path = "toppings_text";
@@ -49,7 +50,7 @@ pub fn edit_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argumen
                 .open(file_path)?
             );
             clear_screen(stdout)?;
-            writeln!(stdout, "\x1b[1;35mDatei geleert.\x1b[0m")?;
+            writeln!(stdout, "{}", theme().notice.paint("Datei geleert."))?;
             first = true;
             continue;
         }
@@ -67,7 +68,7 @@ pub fn edit_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argumen
 
         //Name abfragen
         let topping_name = {
-            let input = prompt_toppings(stdin, stdout, "\x1b[4;34mName\x1b[0m: ")?;
+            let input = prompt_toppings(stdin, stdout, &format!("{}: ", theme().prompt.paint("Name")))?;
             if input.eq_ignore_ascii_case("q") {
                 clear_screen(stdout)?;
                 writeln!(stdout, "Beende Eingabe.")?;
@@ -83,7 +84,7 @@ pub fn edit_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argumen
 
         //Preis abfragen
         let topping_price: u32 = loop {
-            let input = prompt_toppings(stdin, stdout, "\x1b[4;34mPreis\x1b[0m (Ganzzahl): ")?;
+            let input = prompt_toppings(stdin, stdout, &format!("{} (Ganzzahl): ", theme().prompt.paint("Preis")))?;
             let input = input.trim();
 
             if input.eq_ignore_ascii_case("q") {
@@ -107,7 +108,7 @@ pub fn edit_toppings(stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Argumen
         writer.flush()?;
 
         clear_screen(stdout)?;
-        writeln!(stdout, "Erfolgreich hinzugefügt: \x1b[1;32m{} {}\x1b[0m", topping_name, topping_price)?;
+        writeln!(stdout, "Erfolgreich hinzugefügt: {}", theme().success.paint(&format!("{topping_name} {topping_price}")))?;
     }
 }
 
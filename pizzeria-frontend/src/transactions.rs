@@ -1,40 +1,8 @@
-use std::ffi::CStr;
-use std::ptr;
-use libc::{localtime_r, strftime, time, time_t, tm};
-use crate::types::Topping;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::customer::CustomerDetails;
+use crate::types::{Bundle, CatalogItem, Pizza, Placement, Topping};
 
-
-//Zeitstempel über libc
-fn now_local_timestamp() -> String {
-    unsafe {
-        let now_time: time_t = time(ptr::null_mut());
-        let mut local_time: tm = std::mem::zeroed();
-
-        if localtime_r(&now_time, &mut local_time).is_null() {
-            return String::from("1970-01-01 00:00:00");     //Fallback
-        }
-
-        //"YYYY-MM-DD HH:MM:SS" -> max 19 + NUL
-        let mut buf = [0u8; 32];
-        let format = b"%Y-%m-%d %H:%M:%S\0";
-
-        let written = strftime(
-            buf.as_mut_ptr() as *mut _,
-            buf.len(),
-            format.as_ptr() as *const _,
-            &local_time as *const tm,
-        );
-
-        if written == 0 {
-            return String::from("1970-01-01 00:00:00");     //Fallback
-        }
-
-        // C-String -> Rust-String
-        let cstr = CStr::from_ptr(buf.as_ptr() as *const i8);
-        cstr.to_string_lossy().into_owned()
-
-    }
-}
+pub use pizzeria_shared::transaction_log::{escape_transaction_field, join_transaction_items, now_local_timestamp, split_transaction_fields, split_transaction_items};
 
 fn format_eur_cents(cents: u32) -> String {
     let euros = cents / 100;
@@ -42,45 +10,267 @@ fn format_eur_cents(cents: u32) -> String {
     format!("{euros},{cents:02}")
 }
 
-pub fn format_transaction_as_string(price_cents: u32, name: &str) -> String {
+//Bestellnotiz als optionales viertes Feld
+pub fn format_transaction_as_string(price_cents: u32, name: &str, note: Option<&str>) -> String {
     let now = now_local_timestamp();
-    let clean_name = name.replace(['\n', '\r'], " ");
-    format!("{now};{};{}", format_eur_cents(price_cents), clean_name).to_string()
+    let mut line = format!("{now};{};{}", format_eur_cents(price_cents), escape_transaction_field(name));
+    if let Some(note) = note {
+        line.push(';');
+        line.push_str(&escape_transaction_field(note));
+    }
+    line
 }
 
-pub fn build_custom_name(available: &[Topping], qty: &[u32], include_qty: bool) -> String {
-    let mut parts = Vec::new();
+//Bestellung mit Kundendaten:
+//Zeitstempel;Preis;Name;Notiz;Bestellart;Kunde;Telefon;Adresse (leere Felder erlaubt)
+pub fn format_order_transaction_string(price_cents: u32, name: &str, note: Option<&str>, customer: &CustomerDetails) -> String {
+    let fields = [
+        note.unwrap_or(""),
+        customer.order_type.code(),
+        &customer.name,
+        &customer.phone,
+        customer.address.as_deref().unwrap_or(""),
+    ];
+    let mut line = format_transaction_as_string(price_cents, name, None);
+    for field in fields {
+        line.push(';');
+        line.push_str(&escape_transaction_field(field));
+    }
+    line
+}
+
+pub fn build_custom_name(available: &[Topping], qty: &[u32], placement: &[Placement], include_qty: bool) -> String {
+    let mut whole = Vec::new();
+    let mut left = Vec::new();
+    let mut right = Vec::new();
     for (i, &q) in qty.iter().enumerate() {
         if q == 0 { continue; }
         let name = &available[i].name;
-        if include_qty && q > 1 {
-            parts.push(format!("{name} x{q}"));
+        let part = if include_qty && q > 1 {
+            format!("{name} x{q}")
         } else {
-            parts.push(name.clone());
+            name.clone()
+        };
+        match placement[i] {
+            Placement::Whole => whole.push(part),
+            Placement::Left => left.push(part),
+            Placement::Right => right.push(part),
         }
     }
-    if parts.is_empty() {
+
+    //Hälften werden getrennt aufgeführt: "Custom-Pizza (Corn / left: Ham / right: Onions)"
+    let mut groups = Vec::new();
+    if !whole.is_empty() {
+        groups.push(whole.join(", "));
+    }
+    if !left.is_empty() {
+        groups.push(format!("left: {}", left.join(", ")));
+    }
+    if !right.is_empty() {
+        groups.push(format!("right: {}", right.join(", ")));
+    }
+
+    if groups.is_empty() {
         "Custom-Pizza".to_string()
     } else {
-        format!("Custom-Pizza ({})", parts.join(", "))
+        format!("Custom-Pizza ({})", groups.join(" / "))
     }
 }
 
-fn calc_custom_total_cents(base_price_eur: u32, available: &[Topping], qty: &[u32]) -> u32 {
-    let toppings_sum_eur: u32 = qty.iter()
+//Toppings für den Bon, z.B. ["Ham x2 (left)", "Corn"]
+pub fn describe_toppings(available: &[Topping], qty: &[u32], placement: &[Placement]) -> Vec<String> {
+    qty.iter()
         .enumerate()
-        .map(|(i, &q)| q * available[i].price)
+        .filter(|(_, q)| **q > 0)
+        .map(|(i, &q)| {
+            let count = if q > 1 { format!(" x{q}") } else { String::new() };
+            let half = if placement[i] == Placement::Whole { String::new() } else { format!(" ({})", placement[i].label()) };
+            format!("{}{}{}", available[i].name, count, half)
+        })
+        .collect()
+}
+
+pub fn calc_custom_total_cents(base_price_eur: u32, available: &[Topping], qty: &[u32], placement: &[Placement]) -> u32 {
+    let toppings_sum_cents: u32 = qty.iter()
+        .enumerate()
+        .map(|(i, &q)| q * placement[i].price_cents(available[i].price))
         .sum();
-    (base_price_eur + toppings_sum_eur) * 100
-}
-
-pub fn format_custom_pizza_as_transaction_string(
-    base_price_eur: u32,
-    available: &[Topping],
-    qty: &[u32],        //Anzahl Toppings
-    include_qty_in_name: bool
-) -> String {
-    let name = build_custom_name(available, qty, include_qty_in_name);
-    let total_cents = calc_custom_total_cents(base_price_eur, available, qty);
-    format_transaction_as_string(total_cents, &name)
+    base_price_eur * 100 + toppings_sum_cents
+}
+
+//Bestellzeile für POST /order/price
+pub fn format_prebuilt_order_line(pizza: &Pizza) -> String {
+    format!("prebuilt#{}", pizza.name)
+}
+
+pub fn format_item_order_line(item: &CatalogItem) -> String {
+    format!("item#{}", item.name)
+}
+
+fn format_topping_list(available: &[Topping], qty: &[u32], placement: &[Placement]) -> String {
+    let mut names = Vec::new();
+    for (i, &q) in qty.iter().enumerate() {
+        for _ in 0..q {
+            names.push(format!("{}{}", available[i].name, placement[i].suffix()));
+        }
+    }
+    names.join("|")
+}
+
+pub fn format_custom_order_line(available: &[Topping], qty: &[u32], placement: &[Placement]) -> String {
+    format!("custom#{}", format_topping_list(available, qty, placement))
+}
+
+//angepasste Prebuilt-Pizza: Vorlage + vollständige Toppingliste
+pub fn format_customized_order_line(pizza: &Pizza, available: &[Topping], qty: &[u32], placement: &[Placement]) -> String {
+    format!("prebuilt#{}#{}", pizza.name, format_topping_list(available, qty, placement))
+}
+
+//Menge je verfügbarem Topping auf einer Prebuilt-Pizza
+pub fn prebuilt_quantities(pizza: &Pizza, available: &[Topping]) -> Vec<u32> {
+    available.iter()
+        .map(|topping| pizza.toppings.iter().filter(|t| t.name == topping.name).count() as u32)
+        .collect()
+}
+
+//Änderungen gegenüber der Vorlage, z.B. "Hawaii (−Ham, +Xtra Cheese)"
+pub fn build_customized_name(pizza: &Pizza, available: &[Topping], qty: &[u32], placement: &[Placement]) -> String {
+    let original = prebuilt_quantities(pizza, available);
+    let mut changes = Vec::new();
+
+    for (i, topping) in available.iter().enumerate() {
+        let half = if placement[i] == Placement::Whole { String::new() } else { format!(" {}", placement[i].label()) };
+        let count = |diff: u32| if diff > 1 { format!(" x{diff}") } else { String::new() };
+
+        if qty[i] < original[i] {
+            changes.push(format!("\u{2212}{}{}", topping.name, count(original[i] - qty[i])));
+        }
+        if qty[i] > original[i] {
+            changes.push(format!("+{}{}{}", topping.name, count(qty[i] - original[i]), half));
+        } else if qty[i] > 0 && !half.is_empty() {
+            changes.push(format!("{}{}", topping.name, half));
+        }
+    }
+
+    if changes.is_empty() {
+        pizza.name.clone()
+    } else {
+        format!("{} ({})", pizza.name, changes.join(", "))
+    }
+}
+
+pub fn format_bundle_order_line(bundle: &Bundle, choices: &[String]) -> String {
+    format!("bundle#{}#{}", bundle.name, choices.join("|"))
+}
+
+//Lagerbuchung für POST /stock/consume; an der Kennung erkennt das Backend eine wiederholt gesendete Buchung
+pub fn format_stock_booking(key: &str, order: &str) -> String {
+    format!("booking#{key}\n{order}")
+}
+
+//eindeutig je Kasse (Prozess) und Zeitpunkt
+pub fn new_booking_key() -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_nanos()).unwrap_or(0);
+    format!("{}-{nanos}", std::process::id())
+}
+
+pub fn build_bundle_name(bundle: &Bundle, choices: &[String]) -> String {
+    format!("{} ({})", bundle.name, choices.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::customer::OrderType;
+    use crate::types::FoodInfo;
+
+    #[test]
+    fn test_format_custom_order_line_repeats_toppings_by_quantity() {
+        let available = vec![
+            Topping { name: String::from("Ham"), price: 6, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Onions"), price: 2, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Corn"), price: 4, info: FoodInfo::default(), stock: None },
+        ];
+
+        let line = format_custom_order_line(&available, &[2, 0, 1], &[Placement::Whole; 3]);
+
+        assert_eq!(line, "custom#Ham|Ham|Corn");
+    }
+
+    #[test]
+    fn test_stock_booking_starts_with_unique_key() {
+        let first = new_booking_key();
+
+        assert_ne!(first, new_booking_key());
+        assert_eq!(format_stock_booking(&first, "item#Cola\ncustom#Ham"), format!("booking#{first}\nitem#Cola\ncustom#Ham"));
+    }
+
+    #[test]
+    fn test_transaction_fields_are_escaped() {
+        assert_eq!(escape_transaction_field("cut in 8; well done\nno salt"), "cut in 8\\; well done\\nno salt");
+        assert_eq!(escape_transaction_field("a\\b\r"), "a\\\\b\\r");
+
+        let line = format_transaction_as_string(1250, "Hawaii [a;b]", Some("Ring twice;\n"));
+        assert!(line.ends_with(";12,50;Hawaii [a\\;b];Ring twice\\;\\n"));
+    }
+
+    #[test]
+    fn test_split_transaction_fields_reverses_escaping() {
+        let line = format_transaction_as_string(1250, "Hawaii [a;b]", Some("Ring twice;\n\\"));
+        let fields = split_transaction_fields(&line);
+
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[1], "12,50");
+        assert_eq!(fields[2], "Hawaii [a;b]");
+        assert_eq!(fields[3], "Ring twice;\n\\");
+    }
+
+    #[test]
+    fn test_order_transaction_contains_customer_details() {
+        let customer = CustomerDetails {
+            order_type: OrderType::Delivery,
+            name: String::from("Salvatore"),
+            phone: String::from("0176 1234567"),
+            address: Some(String::from("Hauptstr. 1; Hinterhaus")),
+        };
+
+        let line = format_order_transaction_string(1800, "Hawaii", None, &customer);
+
+        assert!(line.ends_with(";18,00;Hawaii;;delivery;Salvatore;0176 1234567;Hauptstr. 1\\; Hinterhaus"));
+    }
+
+    #[test]
+    fn test_custom_pizza_halves() {
+        let available = vec![
+            Topping { name: String::from("Ham"), price: 6, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Onions"), price: 2, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Corn"), price: 4, info: FoodInfo::default(), stock: None },
+        ];
+        let qty = [2, 1, 1];
+        let placement = [Placement::Left, Placement::Right, Placement::Whole];
+
+        assert_eq!(format_custom_order_line(&available, &qty, &placement), "custom#Ham@L|Ham@L|Onions@R|Corn");
+        assert_eq!(build_custom_name(&available, &qty, &placement, true), "Custom-Pizza (Corn / left: Ham x2 / right: Onions)");
+        assert_eq!(calc_custom_total_cents(6, &available, &qty, &placement), 600 + 600 + 100 + 400);
+    }
+
+    #[test]
+    fn test_customized_prebuilt() {
+        let available = vec![
+            Topping { name: String::from("Ham"), price: 6, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Xtra Cheese"), price: 8, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("A-Pineapple"), price: 5, info: FoodInfo::default(), stock: None },
+        ];
+        let hawaii = Pizza {
+            name: String::from("Hawaii"),
+            toppings: vec![available[0].clone(), available[2].clone()],
+            base_price: 4,
+        };
+        let qty = [0, 1, 1];
+
+        assert_eq!(prebuilt_quantities(&hawaii, &available), vec![1, 0, 1]);
+        assert_eq!(build_customized_name(&hawaii, &available, &qty, &[Placement::Whole; 3]), "Hawaii (\u{2212}Ham, +Xtra Cheese)");
+        assert_eq!(format_customized_order_line(&hawaii, &available, &qty, &[Placement::Whole; 3]), "prebuilt#Hawaii#Xtra Cheese|A-Pineapple");
+        assert_eq!(build_customized_name(&hawaii, &available, &[1, 0, 1], &[Placement::Whole; 3]), "Hawaii");
+    }
 }
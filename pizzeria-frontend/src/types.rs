@@ -1,4 +1,10 @@
 use std::collections::HashMap;
+pub use pizzeria_shared::food::{Allergen, DietaryFlag, FoodInfo};
+
+pub const DEFAULT_MAX_PER_TOPPING: u32 = 5;
+pub const DEFAULT_MAX_TOTAL_TOPPINGS: u32 = 12;
+//Toppings auf nur einer Hälfte kosten die Hälfte
+pub const HALF_TOPPING_PERCENT: u32 = 50;
 
 pub fn parse_toppings(content: &str) -> Result<Vec<Topping>, String> {
     let mut toppings = Vec::new();
@@ -19,10 +25,15 @@ pub fn parse_toppings(content: &str) -> Result<Vec<Topping>, String> {
 
         let price = price_text.parse::<u32>()
             .map_err(|_| format!("Zeile {}: Ungültiger Preis '{}'", lineno + 1, price_text))?;
+        // optional: <Name>#<Preis>#<vegan|vegetarian|gluten|lactose|…>
+        let info = FoodInfo::parse(split.next().unwrap_or(""))
+            .map_err(|e| format!("Zeile {}: {}", lineno + 1, e))?;
 
         toppings.push(Topping {
             name: name.to_string(),
             price,
+            info,
+            stock: None,
         });
     }
 
@@ -83,16 +94,179 @@ pub fn parse_prebuild_pizza(content: &str, available: &[Topping]) -> Result<Vec<
     Ok(prebuilds)
 }
 
+// Format: <Kategorie>#<Name>#<Preis>, Kategorie: drink, dessert oder side
+pub fn parse_catalog_items(content: &str) -> Result<Vec<CatalogItem>, String> {
+    let mut items = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut split = line.splitn(3, '#');
+        let category_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Kategorie fehlt", lineno + 1))?;
+        let category = match Category::parse(category_text) {
+            Some(Category::Pizza) | None =>
+                return Err(format!("Zeile {}: Ungültige Kategorie '{}'", lineno + 1, category_text)),
+            Some(category) => category,
+        };
+        let name = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Artikel-Name fehlt", lineno + 1))?;
+        let price_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Artikel-Preis fehlt", lineno + 1))?;
+        let price = price_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Preis '{}'", lineno + 1, price_text))?;
+
+        items.push(CatalogItem {
+            name: name.to_string(),
+            category,
+            price,
+        });
+    }
+
+    Ok(items)
+}
+
+// Format: <Bundle-Name>#<Slot1>;<Slot2>;…#<Preis>
+// Slot: <Bezeichnung>:<Artikel1|Artikel2|…>, <Bezeichnung>:* für jede Prebuilt-Pizza
+// oder <Bezeichnung>:*<Kategorie> für jeden Artikel der Kategorie
+pub fn parse_bundles(content: &str, prebuilts: &[Pizza], items: &[CatalogItem]) -> Result<Vec<Bundle>, String> {
+    let mut bundles = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut split = line.splitn(3, '#');
+        let name = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Bundle-Name fehlt", lineno + 1))?;
+        let slots_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Bundle-Slots fehlen", lineno + 1))?;
+        let price_text = split
+            .next()
+            .ok_or_else(|| format!("Zeile {}: Bundle-Preis fehlt", lineno + 1))?;
+        let price = price_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Preis '{}'", lineno + 1, price_text))?;
+
+        let mut slots = Vec::new();
+        for slot in slots_text.split(';').map(|raw_slot| raw_slot.trim()).filter(|raw_slot| !raw_slot.is_empty()) {
+            let (label, allowed_names) = slot.split_once(':')
+                .ok_or_else(|| format!("Zeile {}: Ungültiger Slot '{}'", lineno + 1, slot))?;
+
+            let allowed: Vec<String> = match allowed_names.trim().strip_prefix('*') {
+                Some("") => prebuilts.iter().map(|pizza| pizza.name.clone()).collect(),
+                Some(category_text) => {
+                    let category = Category::parse(category_text)
+                        .ok_or_else(|| format!("Zeile {}: Unbekannte Kategorie '{}'", lineno + 1, category_text))?;
+                    items.iter()
+                        .filter(|item| item.category == category)
+                        .map(|item| item.name.clone())
+                        .collect()
+                }
+                None => {
+                    let mut allowed = Vec::new();
+                    for item in allowed_names.split('|').map(|raw_item| raw_item.trim()).filter(|raw_item| !raw_item.is_empty()) {
+                        let known = prebuilts.iter().any(|pizza| pizza.name == item)
+                            || items.iter().any(|catalog_item| catalog_item.name == item);
+                        if !known {
+                            return Err(format!("Zeile {}: Unbekannter Artikel '{}'", lineno + 1, item));
+                        }
+                        allowed.push(item.to_string());
+                    }
+                    allowed
+                }
+            };
+
+            slots.push(BundleSlot { label: label.trim().to_string(), allowed });
+        }
+
+        if slots.is_empty() {
+            return Err(format!("Zeile {}: Bundle ohne Slots", lineno + 1));
+        }
+
+        bundles.push(Bundle {
+            name: name.to_string(),
+            slots,
+            price,
+        });
+    }
+
+    Ok(bundles)
+}
+
+// Format: <Topping>#<Min>#<Max> je Topping oder *#<Min>#<Max> für die Gesamtzahl pro Pizza
+pub fn parse_topping_limits(content: &str) -> Result<ToppingLimits, String> {
+    let mut limits = ToppingLimits::default();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // gleiche Regeln wie im Backend (catalog.rs), damit beide dieselben Grenzen annehmen
+        let fields: Vec<&str> = line.split('#').map(str::trim).collect();
+        let [name, min_text, max_text] = fields[..] else {
+            return Err(format!("Zeile {}: erwartet 'Topping#Min#Max'", lineno + 1));
+        };
+        if name.is_empty() {
+            return Err(format!("Zeile {}: Topping-Name fehlt", lineno + 1));
+        }
+        let min = min_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiges Minimum '{}'", lineno + 1, min_text))?;
+        let max = max_text.parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiges Maximum '{}'", lineno + 1, max_text))?;
+        if min > max {
+            return Err(format!("Zeile {}: Minimum {} größer als Maximum {}", lineno + 1, min, max));
+        }
+
+        if name == "*" {
+            limits.min_total = min;
+            limits.max_total = max;
+        } else {
+            limits.per_topping.push(ToppingLimit { name: name.to_string(), min, max });
+        }
+    }
+
+    Ok(limits)
+}
+
+//Teig, Tomatensoße und Käse jeder Pizza: vegetarisch, enthält Gluten und Laktose
+pub fn pizza_base_info() -> FoodInfo {
+    FoodInfo { vegetarian: true, vegan: false, allergens: vec![Allergen::Gluten, Allergen::Lactose] }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Topping {
     pub name: String,
-    pub price: u32
+    pub price: u32,
+    pub info: FoodInfo,
+    //Lagerbestand laut Backend; None = wird nicht geführt
+    pub stock: Option<u32>,
 }
 
 impl Topping {
 
-    pub fn shortname(&self) -> char {
-        self.name.chars().next().expect("There must be a topping-name!")
+    pub fn is_sold_out(&self) -> bool {
+        self.stock == Some(0)
+    }
+
+    //prüft, ob eine weitere Portion vorrätig ist
+    pub fn check_stock(&self, quantity: u32) -> Result<(), String> {
+        match self.stock {
+            Some(0) => Err(format!("{} ist ausverkauft.", self.name)),
+            Some(stock) if quantity >= stock => Err(format!("Nur noch {stock}x {} vorrätig.", self.name)),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -100,12 +274,64 @@ impl Clone for Topping {
     fn clone(&self) -> Self {
         Topping {
             name: Clone::clone(&self.name),
-            price: self.price
+            price: self.price,
+            info: self.info.clone(),
+            stock: self.stock,
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+//Lage eines Toppings auf der Pizza (Half-and-Half)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Placement {
+    Left,
+    Whole,
+    Right,
+}
+
+impl Placement {
+
+    pub fn move_left(self) -> Placement {
+        match self {
+            Placement::Right => Placement::Whole,
+            _ => Placement::Left,
+        }
+    }
+
+    pub fn move_right(self) -> Placement {
+        match self {
+            Placement::Left => Placement::Whole,
+            _ => Placement::Right,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Placement::Left => "left",
+            Placement::Whole => "whole",
+            Placement::Right => "right",
+        }
+    }
+
+    //Kennung in der Bestellzeile für POST /order/price
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Placement::Left => "@L",
+            Placement::Whole => "",
+            Placement::Right => "@R",
+        }
+    }
+
+    //Preis in Cent für ein Topping mit diesem Euro-Preis
+    pub fn price_cents(&self, price: u32) -> u32 {
+        match self {
+            Placement::Whole => price * 100,
+            _ => price * HALF_TOPPING_PERCENT,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Pizza {
     pub name: String,
     pub toppings: Vec<Topping>,
@@ -120,11 +346,193 @@ impl Pizza {
             .sum::<u32>();
         self.base_price + total_topping_price
     }
+
+    pub fn food_info(&self) -> FoodInfo {
+        FoodInfo::combine(&pizza_base_info(), self.toppings.iter().map(|topping| &topping.info))
+    }
+
+    //Toppings, von denen nicht genug für diese Pizza vorrätig ist
+    pub fn sold_out_toppings(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for topping in &self.toppings {
+            let needed = self.toppings.iter().filter(|other| other.name == topping.name).count() as u32;
+            if topping.stock.is_some_and(|stock| stock < needed) && !names.contains(&topping.name.as_str()) {
+                names.push(&topping.name);
+            }
+        }
+        names
+    }
+
+    pub fn is_sold_out(&self) -> bool {
+        !self.sold_out_toppings().is_empty()
+    }
+}
+
+// Format: <Topping>#<Bestand>; nicht aufgeführte Toppings werden nicht gezählt
+pub fn parse_stock(content: &str) -> Result<Vec<(String, u32)>, String> {
+    let mut stock = Vec::new();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (name, count_text) = line.split_once('#')
+            .ok_or_else(|| format!("Zeile {}: Bestand fehlt", lineno + 1))?;
+        let count = count_text.trim().parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Bestand '{}'", lineno + 1, count_text.trim()))?;
+        stock.push((name.trim().to_string(), count));
+    }
+
+    Ok(stock)
+}
+
+pub fn apply_stock(toppings: &mut [Topping], stock: &[(String, u32)]) {
+    for topping in toppings {
+        topping.stock = stock.iter()
+            .find(|(name, _)| *name == topping.name)
+            .map(|(_, count)| *count);
+    }
+}
+
+//prüft die gewählten Mengen einer Custom-Pizza gegen den Bestand
+pub fn check_stock(available: &[Topping], quantity: &[u32]) -> Result<(), String> {
+    for (topping, &q) in available.iter().zip(quantity).filter(|(_, q)| **q > 0) {
+        match topping.stock {
+            Some(0) => return Err(format!("{} ist ausverkauft.", topping.name)),
+            Some(stock) if q > stock => return Err(format!("Nur noch {stock}x {} vorrätig.", topping.name)),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ToppingLimit {
+    pub name: String,
+    pub min: u32,
+    pub max: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ToppingLimits {
+    pub min_total: u32,
+    pub max_total: u32,
+    pub per_topping: Vec<ToppingLimit>,
+}
+
+impl Default for ToppingLimits {
+    fn default() -> Self {
+        Self {
+            min_total: 0,
+            max_total: DEFAULT_MAX_TOTAL_TOPPINGS,
+            per_topping: Vec::new(),
+        }
+    }
+}
+
+impl ToppingLimits {
+
+    fn limit(&self, name: &str) -> Option<&ToppingLimit> {
+        self.per_topping.iter().find(|limit| limit.name == name)
+    }
+
+    pub fn min_for(&self, name: &str) -> u32 {
+        self.limit(name).map(|limit| limit.min).unwrap_or(0)
+    }
+
+    pub fn max_for(&self, name: &str) -> u32 {
+        self.limit(name).map(|limit| limit.max).unwrap_or(DEFAULT_MAX_PER_TOPPING)
+    }
+
+    //prüft, ob ein weiteres Topping dieser Sorte erlaubt ist
+    pub fn check_add(&self, name: &str, quantity: u32, total: u32) -> Result<(), String> {
+        let max = self.max_for(name);
+        if quantity >= max {
+            return Err(format!("Maximal {max}x {name} pro Pizza."));
+        }
+        if total >= self.max_total {
+            return Err(format!("Maximal {} Toppings pro Pizza.", self.max_total));
+        }
+        Ok(())
+    }
+
+    //prüft die Mindestmengen vor dem Checkout, angepasste Vorlagen brauchen keine Mindestanzahl
+    pub fn check_pizza(&self, available: &[Topping], quantity: &[u32], customized: bool) -> Result<(), String> {
+        for limit in self.per_topping.iter().filter(|limit| limit.min > 0) {
+            let count = available.iter()
+                .zip(quantity)
+                .find(|(topping, _)| topping.name == limit.name)
+                .map(|(_, q)| *q)
+                .unwrap_or(0);
+            if count < limit.min {
+                return Err(format!("Mindestens {}x {} erforderlich.", limit.min, limit.name));
+            }
+        }
+
+        let total: u32 = quantity.iter().sum();
+        if !customized && total < self.min_total {
+            return Err(format!("Mindestens {} Toppings pro Pizza erforderlich.", self.min_total));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Category {
+    Pizza,
+    Drink,
+    Dessert,
+    Side,
+}
+
+impl Category {
+    pub const ALL: [Category; 4] = [Category::Pizza, Category::Drink, Category::Dessert, Category::Side];
+
+    pub fn parse(text: &str) -> Option<Category> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "pizza" => Some(Category::Pizza),
+            "drink" => Some(Category::Drink),
+            "dessert" => Some(Category::Dessert),
+            "side" => Some(Category::Side),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Category::Pizza => "Pizza",
+            Category::Drink => "Drinks",
+            Category::Dessert => "Desserts",
+            Category::Side => "Sides",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CatalogItem {
+    pub name: String,
+    pub category: Category,
+    pub price: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BundleSlot {
+    pub label: String,
+    pub allowed: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Bundle {
+    pub name: String,
+    pub slots: Vec<BundleSlot>,
+    pub price: u32,
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{parse_toppings, Pizza, Topping};
+    use crate::types::{apply_stock, check_stock, parse_bundles, parse_catalog_items, parse_prebuild_pizza, parse_stock, parse_topping_limits, parse_toppings, pizza_base_info, Allergen, Bundle, BundleSlot, CatalogItem, Category, DietaryFlag, FoodInfo, Pizza, Topping, DEFAULT_MAX_PER_TOPPING};
 
     #[test]
     fn test_total_price_of_a_pizza_without_toppings() {
@@ -144,8 +552,8 @@ mod tests {
         let pizza = Pizza {
             name: String::new(),
             toppings: vec![
-                Topping { name: String::from("Test_Cheese"), price: 14 },
-                Topping { name: String::from("Test_Tomato"), price: 30}
+                Topping { name: String::from("Test_Cheese"), price: 14, info: FoodInfo::default(), stock: None },
+                Topping { name: String::from("Test_Tomato"), price: 30, info: FoodInfo::default(), stock: None }
             ],
             base_price: 10
         };
@@ -171,7 +579,7 @@ Ham#8
         "#)?;
 
         assert_eq!(toppings, vec![
-            Topping { name: String::from("Ham"), price: 8 }
+            Topping { name: String::from("Ham"), price: 8, info: FoodInfo::default(), stock: None }
         ]);
 
         Ok(())
@@ -187,11 +595,161 @@ Brocoli#4
         "#)?;
 
         assert_eq!(toppings, vec![
-            Topping { name: String::from("Ham"), price: 8 },
-            Topping { name: String::from("Cheese"), price: 3 },
-            Topping { name: String::from("Brocoli"), price: 4 },
+            Topping { name: String::from("Ham"), price: 8, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Cheese"), price: 3, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Brocoli"), price: 4, info: FoodInfo::default(), stock: None },
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_catalog_items() -> Result<(), Box<dyn std::error::Error>> {
+
+        let items = parse_catalog_items(r#"
+drink#Cola#3
+dessert#Tiramisu#5
+        "#)?;
+
+        assert_eq!(items, vec![
+            CatalogItem { name: String::from("Cola"), category: Category::Drink, price: 3 },
+            CatalogItem { name: String::from("Tiramisu"), category: Category::Dessert, price: 5 },
         ]);
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_catalog_items_rejects_pizza_category() {
+
+        assert!(parse_catalog_items("pizza#Calzone#9").is_err());
+    }
+
+    #[test]
+    fn test_parse_bundles_resolves_wildcard_slots() -> Result<(), Box<dyn std::error::Error>> {
+
+        let prebuilts = vec![
+            Pizza { name: String::from("Margherita"), toppings: vec![], base_price: 4 },
+            Pizza { name: String::from("Funghi"), toppings: vec![], base_price: 4 },
+        ];
+        let items = vec![
+            CatalogItem { name: String::from("Cola"), category: Category::Drink, price: 3 },
+            CatalogItem { name: String::from("Tiramisu"), category: Category::Dessert, price: 5 },
+        ];
+
+        let bundles = parse_bundles("Family Deal#Pizza:*;Pizza:Funghi;Drink:*drink#14", &prebuilts, &items)?;
+
+        assert_eq!(bundles, vec![
+            Bundle {
+                name: String::from("Family Deal"),
+                slots: vec![
+                    BundleSlot { label: String::from("Pizza"), allowed: vec![String::from("Margherita"), String::from("Funghi")] },
+                    BundleSlot { label: String::from("Pizza"), allowed: vec![String::from("Funghi")] },
+                    BundleSlot { label: String::from("Drink"), allowed: vec![String::from("Cola")] },
+                ],
+                price: 14,
+            }
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_bundles_rejects_unknown_pizza() {
+
+        let result = parse_bundles("Duo Deal#Pizza:Calzone#14", &[], &[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_topping_limits_cap_quantity_and_total() -> Result<(), Box<dyn std::error::Error>> {
+
+        let limits = parse_topping_limits("*#1#3\nHam#0#2")?;
+
+        assert!(limits.check_add("Ham", 1, 1).is_ok());
+        assert_eq!(limits.check_add("Ham", 2, 2), Err(String::from("Maximal 2x Ham pro Pizza.")));
+        assert_eq!(limits.check_add("Corn", 0, 3), Err(String::from("Maximal 3 Toppings pro Pizza.")));
+        assert_eq!(limits.max_for("Corn"), DEFAULT_MAX_PER_TOPPING);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_topping_limits_require_minimums() -> Result<(), Box<dyn std::error::Error>> {
+
+        let limits = parse_topping_limits("*#1#8\nXtra Cheese#1#3")?;
+        let available = vec![
+            Topping { name: String::from("Ham"), price: 6, info: FoodInfo::default(), stock: None },
+            Topping { name: String::from("Xtra Cheese"), price: 8, info: FoodInfo::default(), stock: None },
+        ];
+
+        assert_eq!(limits.check_pizza(&available, &[1, 0], false), Err(String::from("Mindestens 1x Xtra Cheese erforderlich.")));
+        assert!(limits.check_pizza(&available, &[0, 1], false).is_ok());
+
+        let limits = parse_topping_limits("*#1#8")?;
+        assert_eq!(limits.check_pizza(&available, &[0, 0], false), Err(String::from("Mindestens 1 Toppings pro Pizza erforderlich.")));
+        assert!(limits.check_pizza(&available, &[0, 0], true).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reject_invalid_topping_limits() {
+        assert_eq!(parse_topping_limits("Ham#3#2").unwrap_err(), "Zeile 1: Minimum 3 größer als Maximum 2");
+        assert!(parse_topping_limits("*#1#8\n*#5#4").is_err());
+        assert!(parse_topping_limits("Ham#1").is_err());
+        assert!(parse_topping_limits("#1#2").is_err());
+    }
+
+    #[test]
+    fn test_parse_toppings_with_food_info() {
+        let toppings = parse_toppings("Corn#4#vegan\nXtra Cheese#8#vegetarian|lactose\nHam#6").unwrap();
+
+        assert!(toppings[0].info.vegan && toppings[0].info.vegetarian);
+        assert_eq!(toppings[1].info.allergens, vec![Allergen::Lactose]);
+        assert_eq!(toppings[1].info.to_tags(), "vegetarian|lactose");
+        assert_eq!(toppings[2].info, FoodInfo::default());
+        assert!(parse_toppings("Corn#4#spicy").is_err());
+    }
+
+    #[test]
+    fn test_pizza_food_info_requires_all_toppings() {
+        let toppings = parse_toppings("Corn#4#vegan\nXtra Cheese#8#vegetarian|lactose\nHam#6").unwrap();
+        let pizza = |toppings: Vec<Topping>| Pizza { name: String::from("Test"), toppings, base_price: 4 };
+
+        let corn = pizza(vec![toppings[0].clone()]).food_info();
+        assert!(corn.has(DietaryFlag::Vegetarian));
+        assert!(!corn.has(DietaryFlag::Vegan));
+        assert_eq!(corn.allergen_label(), "Gl,La");
+
+        let cheese = pizza(vec![toppings[0].clone(), toppings[1].clone()]).food_info();
+        assert_eq!(cheese.diet_label(), "vegetarian");
+        assert_eq!(cheese.allergen_label(), "Gl,La");
+
+        let ham = pizza(vec![toppings[0].clone(), toppings[2].clone()]).food_info();
+        assert!(!ham.has(DietaryFlag::Vegetarian));
+
+        assert_eq!(pizza(vec![]).food_info(), pizza_base_info());
+    }
+
+    #[test]
+    fn test_stock_marks_sold_out_toppings_and_pizzas() -> Result<(), Box<dyn std::error::Error>> {
+
+        let mut toppings = parse_toppings("Ham#6\nMushrooms#7\nCorn#4")?;
+        apply_stock(&mut toppings, &parse_stock("Ham#1\nMushrooms#0")?);
+        let pizzas = parse_prebuild_pizza("Funghi#Mushrooms#4\nDouble Ham#Ham|Ham#4\nCorny#Corn|Ham#4", &toppings)?;
+
+        assert_eq!(pizzas[0].sold_out_toppings(), vec!["Mushrooms"]);
+        assert_eq!(pizzas[1].sold_out_toppings(), vec!["Ham"]);
+        assert!(!pizzas[2].is_sold_out());
+
+        assert_eq!(toppings[0].check_stock(1), Err(String::from("Nur noch 1x Ham vorrätig.")));
+        assert_eq!(toppings[1].check_stock(0), Err(String::from("Mushrooms ist ausverkauft.")));
+        assert!(toppings[2].check_stock(9).is_ok());
+        assert!(check_stock(&toppings, &[1, 0, 3]).is_ok());
+        assert!(check_stock(&toppings, &[2, 0, 0]).is_err());
+
+        Ok(())
+    }
 }
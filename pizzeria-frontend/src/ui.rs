@@ -1,47 +1,68 @@
 use std::io;
-use std::io::{Read, Stdin, Stdout, Write};
+use std::io::{Stdin, Stdout, Write};
+use crate::screen;
+use crate::input::{read_input, InputEvent, MouseButton, MouseKind};
+
+//Eingabe über den Decoder aus input.rs, damit Escape-Sequenzen (Pfeiltasten, Mausklicks)
+//nicht als Text im Eingabefeld landen
+fn next_event(stdin: &mut Stdin) -> io::Result<InputEvent> {
+    read_input(stdin).map_err(|error| io::Error::other(error.to_string()))
+}
+
+//nach Resize oder Ctrl+Z/fg ist der Bildschirm leer bzw. verschoben: Meldung und Eingabe neu ausgeben
+fn redraw(stdout: &mut Stdout, text: &str) -> io::Result<()> {
+    write!(stdout, "\x1B[2J\x1B[1;1H{text}")?;
+    stdout.flush()
+}
 
 pub fn wait_enter(stdout: &mut Stdout, stdin: &mut Stdin, msg: &str) -> io::Result<()> {
+    // Meldung steht unter dem Menü, danach muss es vollständig neu gezeichnet werden
+    screen::invalidate();
     write!(stdout, "{msg}")?;
     stdout.flush()?;
 
-    let mut b = [0u8; 1];
     loop {
-        let n = stdin.read(&mut b)?;
-        if n == 0 { break; }
-        if b[0] == b'\r' || b[0] == b'\n' { break; }
+        match next_event(stdin)? {
+            // Tippen auf den Bildschirm bestätigt ebenfalls
+            InputEvent::Enter | InputEvent::Exit => break,
+            InputEvent::Mouse(mouse) if mouse.kind == MouseKind::Press(MouseButton::Left) => break,
+            InputEvent::Resize => redraw(stdout, msg)?,
+            _ => {}
+        }
     }
     Ok(())
 }
 
+//Ctrl+C bricht ab (ErrorKind::Interrupted) statt den bisher getippten Text zu übernehmen
 pub fn prompt(stdin: &mut Stdin, stdout: &mut Stdout, label: &str) -> io::Result<String> {
+    screen::invalidate();
     write!(stdout, "{}", label)?;
     stdout.flush()?;
 
-    let mut buf = Vec::new();
-    let mut byte = [0u8; 1];
+    let mut buf = String::new();
 
     loop {
-        let n = stdin.read(&mut byte)?;
-        if n == 0 { break; }
-        match byte[0] {
-            b'\r' | b'\n' => break,
-            8 | 127 => { // Backspace
-                if !buf.is_empty() {
-                    buf.pop();
-                    write!(stdout, "\x08 \x08")?;
-                    stdout.flush()?;
-                }
+        match next_event(stdin)? {
+            InputEvent::Enter => break,
+            InputEvent::Exit => {
+                writeln!(stdout)?;
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "Eingabe abgebrochen"));
+            }
+            InputEvent::Back if buf.pop().is_some() => {
+                write!(stdout, "\x08 \x08")?;
+                stdout.flush()?;
             }
-            b => {
-                buf.push(b);
-                stdout.write_all(&[b])?;
+            InputEvent::Char(c) => {
+                buf.push(c);
+                write!(stdout, "{c}")?;
                 stdout.flush()?;
             }
+            InputEvent::Resize => redraw(stdout, &format!("{label}{buf}"))?,
+            _ => {}
         }
     }
     writeln!(stdout)?;
-    Ok(String::from_utf8_lossy(&buf).trim().to_string())
+    Ok(buf.trim().to_string())
 }
 
 pub fn confirm(stdin: &mut Stdin, stdout: &mut Stdout, question: &str) -> io::Result<bool> {
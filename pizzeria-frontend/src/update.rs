@@ -1,32 +1,129 @@
 use std::error::Error;
-use std::io::{Stdin, Stdout, Write};
+use std::io::{ErrorKind, Stdin, Stdout, Write};
 use crate::{clear_screen, Arguments};
 use crate::table::{Table, TableCell, TableRow};
 use crate::table::Align::Right;
 use crate::table_menu::TableMenu;
-use crate::types::{Pizza, Topping};
-use crate::custom_toppings::{add_toppings, list_toppings_from_backend, remove_topping, send_clear_toppings};
-use crate::http::send_transaction_record;
-use crate::input::{read_input, InputEvent};
+use crate::types::{check_stock, pizza_base_info, Bundle, DietaryFlag, FoodInfo, Pizza, Placement, Topping, ToppingLimits};
+use crate::custom_toppings::{add_toppings, browse_toppings, remove_topping, restock_topping, send_clear_toppings};
+use crate::http::{get, send_refund, send_stock_change, send_transaction_record};
+use crate::input::{read_input, InputEvent, MouseButton, MouseEvent, MouseKind};
+use crate::filter::{edit_filter, page_row, snap_row, step_row};
+use crate::hotkeys::{assign_hotkeys, hotkey_row};
 use crate::render::render_menu;
-use crate::state::{MenuIndex, State};
-use crate::transactions::{format_custom_pizza_as_transaction_string, format_transaction_as_string};
-use crate::ui::{confirm, wait_enter};
+use crate::style::{theme, Style};
+use crate::state::{CartMenuEntry, MenuIndex, OrderMenuEntry, PendingStockConsume, State};
+use crate::cart::{note_from_input, Cart, CartEntry};
+use crate::customer::{customer_form, DeliverySettings, OrderType};
+use crate::pricing::{discounted_cents, format_price, quote_order, quote_order_line, PricingRule, PricingScope, CUSTOM_BASE_PRICE};
+use crate::transactions::{build_bundle_name, build_custom_name, build_customized_name, calc_custom_total_cents, describe_toppings, format_bundle_order_line, format_custom_order_line, format_customized_order_line, format_item_order_line, format_order_transaction_string, format_prebuilt_order_line, format_stock_booking, new_booking_key, now_local_timestamp, prebuilt_quantities};
+use crate::receipt::{write_receipt, Receipt};
+use crate::history::{parse_sales_report, sales_report_menu, HistoryEntry, HistoryFilter};
+use crate::ui::{confirm, prompt, wait_enter};
 
 pub fn update(input: InputEvent, state: &mut State, stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> bool {
 
     if let InputEvent::Exit = input {
         return true;
     }
+    if let InputEvent::PageUp | InputEvent::PageDown = input {
+        state.move_selection_by_page(matches!(input, InputEvent::PageDown));
+        return false;
+    }
+    let input = match input {
+        InputEvent::Mouse(mouse) => match mouse_update(mouse, state) {
+            Some(input) => input,
+            None => return false,
+        },
+        _ => input,
+    };
+    if filter_update(&input, state) {
+        return false;
+    }
 
-    match state.current_menu {
+    let input = match input {
+        InputEvent::Char(key) => match hotkey_target(state, key) {
+            // Hotkey wählt die Zeile aus und löst sie aus wie Enter
+            Some(row) => {
+                state.set_selected_row(row);
+                state.apply_selection_marker();
+                InputEvent::Enter
+            }
+            None => input,
+        },
+        _ => input,
+    };
+
+    let previous_menu = state.current_menu;
+    let exit = match state.current_menu {
         MenuIndex::MainMenu => main_menu_update(input, state, arguments),
         MenuIndex::EditToppingsMenu => edit_toppings_menu_update(input, state, stdout, stdin, arguments),
-        MenuIndex::OrderMenu => order_menu_update(input, state, stdout, stdin, arguments),
+        MenuIndex::OrderMenu => order_menu_update(input, state),
+        MenuIndex::CategoryMenu => category_menu_update(input, state, stdout, stdin, arguments),
+        MenuIndex::CartMenu => cart_menu_update(input, state, stdout, stdin, arguments),
+        MenuIndex::HistoryMenu => history_menu_update(input, state, stdout, stdin, arguments),
+    };
+    // Suche gilt nur, solange man im Menü bleibt
+    if state.current_menu != previous_menu {
+        state.menus[previous_menu.as_index()].set_filter(None);
     }
+    exit
 }
 
-fn order_menu_update(input: InputEvent, state: &mut State, stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> bool {
+//Mausrad bewegt die Auswahl, ein Klick auf eine Zeile wählt sie aus und löst sie aus wie Enter
+fn mouse_update(mouse: MouseEvent, state: &mut State) -> Option<InputEvent> {
+    match mouse.kind {
+        MouseKind::WheelUp | MouseKind::WheelDown => {
+            state.move_selection_by(1, mouse.kind == MouseKind::WheelDown);
+            None
+        }
+        MouseKind::Press(MouseButton::Left) => {
+            let row = state.current_menu().row_at(mouse.column, mouse.row)?;
+            state.set_selected_row(row);
+            state.apply_selection_marker();
+            Some(InputEvent::Enter)
+        }
+        _ => None,
+    }
+}
+
+//Menüs mit Hotkeys (siehe hotkeys::label_hotkeys); die Historie nutzt Einzeltasten als Befehle
+fn hotkey_target(state: &State, key: char) -> Option<usize> {
+    match state.current_menu {
+        MenuIndex::MainMenu | MenuIndex::EditToppingsMenu | MenuIndex::OrderMenu | MenuIndex::CategoryMenu | MenuIndex::CartMenu => {
+            hotkey_row(state.current_menu().table(), key)
+        }
+        MenuIndex::HistoryMenu => None,
+    }
+}
+
+//Suche im aktuellen Menü (siehe filter.rs); true = Eingabe verarbeitet
+fn filter_update(input: &InputEvent, state: &mut State) -> bool {
+    let mut filter = state.current_menu().filter().map(String::from);
+    if edit_filter(&mut filter, input) {
+        state.current_menu_mut().set_filter(filter);
+        let rows = state.current_menu().visible_rows();
+        state.set_selected_row(snap_row(&rows, state.selected_row()));
+        state.apply_selection_marker();
+        return true;
+    }
+    if filter.is_none() {
+        return false;
+    }
+
+    let rows = state.current_menu().visible_rows();
+    match input {
+        InputEvent::Up | InputEvent::Down => {
+            state.set_selected_row(step_row(&rows, state.selected_row(), matches!(input, InputEvent::Down)));
+            state.apply_selection_marker();
+            true
+        }
+        // ohne Treffer gibt es keine Zeile, auf die Enter & Co. wirken könnten
+        _ => rows.is_empty(),
+    }
+}
+
+fn order_menu_update(input: InputEvent, state: &mut State) -> bool {
 
     match input {
         InputEvent::Up => {
@@ -54,41 +151,272 @@ fn order_menu_update(input: InputEvent, state: &mut State, stdout: &mut Stdout,
         }
         InputEvent::Enter => {
             let sel_row = state.selected_row();
-            let length = state.prebuilt_pizzas.len();
-            let custom_row = length; // letzte Zeile ist Custom (nach n Pizzen eingefügt)
+            if let Some(category) = state.order_categories.get(sel_row).copied() {
+                state.open_category(category);
+                return false;
+            }
+            if sel_row == state.order_categories.len() {
+                state.open_cart();
+                return false;
+            }
+        }
+        // Ernährungsfilter umschalten: aus -> vegetarian -> vegan -> aus
+        InputEvent::Char('v') => {
+            state.dietary_filter = DietaryFlag::next_filter(state.dietary_filter);
+            state.rebuild_order_menu();
+        }
+        _ => {}
+    }
+
+   state.apply_selection_marker();
+
+    false
+}
 
-            if sel_row == custom_row {
-                let base_price = 6;
+fn category_menu_update(input: InputEvent, state: &mut State, stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> bool {
 
-                match order_custom_pizza(stdout, stdin, &state.toppings_catalog, base_price, arguments) {
-                    Ok(Some(line)) => {
-                        state.pending_fallbacks.push(line);
+    match input {
+        InputEvent::Up => {
+            let length = state.current_menu_mut().table_mut().rows_mut().len();
+            let sel_row = state.selected_row();
+            if sel_row > 0 {
+                state.set_selected_row(sel_row -1) ;
+            } else {
+                state.set_selected_row(length.saturating_sub(1));
+            }
+        }
+        InputEvent::Down => {
+            let length = state.current_menu_mut().table_mut().rows_mut().len();
+            let sel_row = state.selected_row();
+            if sel_row + 1 < length {
+                state.set_selected_row(sel_row + 1);
+            } else {
+                state.set_selected_row(0);
+            }
+        }
+        InputEvent::Back => {
+            state.rebuild_order_menu();
+            state.current_menu = MenuIndex::OrderMenu;
+            state.apply_selection_marker();
+            return false;
+        }
+        InputEvent::Right => {
+            // Prebuilt-Pizza als Vorlage für den Custom-Pizza-Dialog
+            if let Some(OrderMenuEntry::Prebuilt(index)) = state.category_menu_entry(state.selected_row()) {
+                let template = state.prebuilt_pizzas[index].clone();
+                custom_pizza_dialog(state, stdout, stdin, Some(&template), arguments);
+            }
+        }
+        InputEvent::Enter => {
+            let sel_row = state.selected_row();
+
+            match state.category_menu_entry(sel_row) {
+                Some(OrderMenuEntry::Custom) => {
+                    custom_pizza_dialog(state, stdout, stdin, None, arguments);
+                }
+                Some(OrderMenuEntry::Bundle(index)) => {
+                    let bundle = state.bundles[index].clone();
+
+                    match order_bundle(stdout, stdin, &bundle, arguments) {
+                        Ok(Some(entry)) => {
+                            state.cart.add(entry);
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            writeln!(stdout, "Fehler im Bundle-Dialog: {e}.").ok();
+                            wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                        }
                     }
-                    Ok(None) => {}
-                    Err(e) => {
-                        writeln!(stdout, "Fehler im Custom-Pizza-Dialog: {e}.").ok();
+                }
+                Some(OrderMenuEntry::Item(index)) => {
+                    let item = &state.catalog_items[index];
+                    let local_cents = discounted_cents(item.price * 100, PricingScope::Item, state.active_pricing_rule.as_ref());
+                    let order_line = format_item_order_line(item);
+                    let price_cents = match quote_order_line(&order_line, local_cents, arguments) {
+                        Ok(cents) => cents,
+                        Err(text) => {
+                            writeln!(stdout, "\n{}: {text}", theme().error.paint("Bestellung abgelehnt")).ok();
+                            wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                            state.apply_selection_marker();
+                            return false;
+                        }
+                    };
+
+                    writeln!(stdout, "\n{}: {} ({}).", theme().success.paint("In den Warenkorb gelegt"), theme().emphasis.paint(&item.name), format_price(price_cents)).ok();
+                    state.cart.add(CartEntry::new(item.name.clone(), order_line, price_cents));
+                    wait_enter(stdout, stdin, "\n[OK mit Enter]").ok();
+                }
+                Some(OrderMenuEntry::Prebuilt(index)) => {
+                    let p = &state.prebuilt_pizzas[index];
+                    if p.is_sold_out() {
+                        writeln!(stdout, "\n{}: {} ({}). Mit [→] ohne diese Toppings anpassen.", theme().error.paint("Ausverkauft"), p.name, p.sold_out_toppings().join(", ")).ok();
                         wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                        state.apply_selection_marker();
+                        return false;
                     }
-                }
+                    let local_cents = discounted_cents(p.total_price() * 100, PricingScope::Prebuilt, state.active_pricing_rule.as_ref());
+                    let order_line = format_prebuilt_order_line(p);
+                    let price_cents = match quote_order_line(&order_line, local_cents, arguments) {
+                        Ok(cents) => cents,
+                        Err(text) => {
+                            writeln!(stdout, "\n{}: {text}", theme().error.paint("Bestellung abgelehnt")).ok();
+                            wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                            state.apply_selection_marker();
+                            return false;
+                        }
+                    };
 
-                if let Err(e) = order_custom_pizza(stdout, stdin, &state.toppings_catalog, base_price, arguments) {
-                    writeln!(stdout, "Fehler im Custom-Pizza-Dialog: {e}.").ok();
+                    writeln!(stdout, "\n{}: {} ({}).", theme().success.paint("In den Warenkorb gelegt"), theme().emphasis.paint(&p.name), format_price(price_cents)).ok();
+                    let toppings = p.toppings.iter().map(|t| t.name.clone()).collect();
+                    state.cart.add(CartEntry::new(p.name.clone(), order_line, price_cents).with_details(toppings));
+                    wait_enter(stdout, stdin, "\n[OK mit Enter]").ok();
+                }
+                None => {
+                    writeln!(stdout, "Ungültige Auswahl.").ok();
                     wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
                 }
-            } else if let Some(p) = state.prebuilt_pizzas.get(sel_row) {
-                writeln!(stdout, "\n\x1b[4;32mBestellung bestätigt\x1b[0m: \x1b[1m{}\x1b[0m ({}.00$).", p.name, p.total_price()).ok();
+            }
+        }
+        _ => {}
+    }
 
-                let price_cents = p.total_price() * 100;
-                let transaction_string = format_transaction_as_string(price_cents, &p.name);
+   state.apply_selection_marker();
 
-                if let Err(e) = send_transaction_record(transaction_string.clone(), arguments) {
-                    writeln!(stdout, "Warnung: Konnte Transaktion nicht an Backend senden: {e}").ok();
-                    state.pending_fallbacks.push(transaction_string);
-                }
-                wait_enter(stdout, stdin, "\n[OK mit Enter]").ok();
+    false
+}
+
+fn custom_pizza_dialog(state: &mut State, stdout: &mut Stdout, stdin: &mut Stdin, template: Option<&Pizza>, arguments: &Arguments) {
+    let rule = state.active_pricing_rule.clone();
+    let limits = state.topping_limits.clone();
+
+    match order_custom_pizza(stdout, stdin, &state.toppings_catalog, template, rule.as_ref(), &limits, arguments) {
+        Ok(Some(entry)) => {
+            state.cart.add(entry);
+        }
+        Ok(None) => {}
+        Err(e) => {
+            writeln!(stdout, "Fehler im Custom-Pizza-Dialog: {e}.").ok();
+            wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+        }
+    }
+}
+
+fn cart_menu_update(input: InputEvent, state: &mut State, stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> bool {
+
+    let sel_row = state.selected_row();
+
+    match input {
+        InputEvent::Up => {
+            let length = state.current_menu_mut().table_mut().rows_mut().len();
+            if sel_row > 0 {
+                state.set_selected_row(sel_row -1) ;
+            } else {
+                state.set_selected_row(length.saturating_sub(1));
+            }
+        }
+        InputEvent::Down => {
+            let length = state.current_menu_mut().table_mut().rows_mut().len();
+            if sel_row + 1 < length {
+                state.set_selected_row(sel_row + 1);
             } else {
-                writeln!(stdout, "Ungültige Auswahl.").ok();
-                wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                state.set_selected_row(0);
+            }
+        }
+        InputEvent::Back => {
+            state.rebuild_order_menu();
+            state.current_menu = MenuIndex::OrderMenu;
+            state.apply_selection_marker();
+            return false;
+        }
+        // Menge ändern bzw. Position entfernen
+        InputEvent::Char('+') => {
+            if let Some(CartMenuEntry::Entry(index)) = state.cart_menu_entry(sel_row) {
+                state.cart.increment(index);
+                state.open_cart();
+            }
+        }
+        InputEvent::Char('-') => {
+            if let Some(CartMenuEntry::Entry(index)) = state.cart_menu_entry(sel_row) {
+                state.cart.decrement(index);
+                state.open_cart();
+            }
+        }
+        InputEvent::Char('x') => {
+            if let Some(CartMenuEntry::Entry(index)) = state.cart_menu_entry(sel_row) {
+                state.cart.remove(index);
+                state.open_cart();
+            }
+        }
+        // Notiz zur Position, z.B. "well done" oder "cut in 8"
+        InputEvent::Char('n') => {
+            if let Some(CartMenuEntry::Entry(index)) = state.cart_menu_entry(sel_row) {
+                match prompt(stdin, stdout, "\nNotiz (leer = entfernen): ") {
+                    Ok(text) => state.cart.set_note(index, note_from_input(&text)),
+                    Err(e) => {
+                        writeln!(stdout, "\nFehler bei der Eingabe: {e}").ok();
+                        wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                    }
+                }
+                state.open_cart();
+            }
+        }
+        InputEvent::Enter => {
+            match state.cart_menu_entry(sel_row) {
+                Some(CartMenuEntry::Checkout) => {
+                    if state.cart.is_empty() {
+                        writeln!(stdout, "\nDer Warenkorb ist leer.").ok();
+                        wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                    } else {
+                        let delivery = state.delivery_settings.clone();
+                        match checkout_cart(stdout, stdin, &mut state.cart, &delivery, &mut state.pending_stock_consumes, arguments) {
+                            Ok(Some(line)) => {
+                                state.pending_fallbacks.push(line);
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                writeln!(stdout, "Fehler beim Checkout: {e}.").ok();
+                                wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                            }
+                        }
+                        if state.cart.is_empty() {
+                            // Verbrauch der Bestellung kann Warnschwellen unterschreiten
+                            state.refresh_inventory_alerts(arguments);
+                            state.rebuild_order_menu();
+                            state.current_menu = MenuIndex::MainMenu;
+                            state.apply_selection_marker();
+                            return false;
+                        }
+                    }
+                    state.open_cart();
+                }
+                Some(CartMenuEntry::Note) => {
+                    match prompt(stdin, stdout, "\nNotiz zur Bestellung (leer = entfernen): ") {
+                        Ok(text) => state.cart.note = note_from_input(&text),
+                        Err(e) => {
+                            writeln!(stdout, "\nFehler bei der Eingabe: {e}").ok();
+                            wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                        }
+                    }
+                    state.open_cart();
+                }
+                Some(CartMenuEntry::Clear) => {
+                    match confirm(stdin, stdout, &theme().confirm_question("Warenkorb wirklich leeren?")) {
+                        Ok(true) => state.cart.clear(),
+                        Ok(false) => {}
+                        Err(e) => {
+                            writeln!(stdout, "\nFehler bei der Eingabe: {e}").ok();
+                            wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                        }
+                    }
+                    state.open_cart();
+                }
+                Some(CartMenuEntry::Back) => {
+                    state.rebuild_order_menu();
+                    state.current_menu = MenuIndex::OrderMenu;
+                    state.apply_selection_marker();
+                    return false;
+                }
+                Some(CartMenuEntry::Entry(_)) | None => {}
             }
         }
         _ => {}
@@ -99,6 +427,224 @@ fn order_menu_update(input: InputEvent, state: &mut State, stdout: &mut Stdout,
     false
 }
 
+//gesamter Warenkorb als eine Transaktion; Rückgabe: Transaktionszeile für das Fallback-Log.
+//Nicht erreichbare Lagerbuchungen landen in pending_consumes und werden beim Öffnen des Order-Menüs nachgeholt
+fn checkout_cart(stdout: &mut Stdout, stdin: &mut Stdin, cart: &mut Cart, delivery: &DeliverySettings, pending_consumes: &mut Vec<PendingStockConsume>, arguments: &Arguments) -> Result<Option<String>, Box<dyn Error>> {
+    clear_screen(stdout)?;
+
+    let customer = match customer_form(stdin, stdout, delivery)? {
+        Some(customer) => customer,
+        None => {
+            writeln!(stdout, "\nAbgebrochen - Bestellung bleibt im Warenkorb.")?;
+            wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+            return Ok(None);
+        }
+    };
+    let is_delivery = customer.order_type == OrderType::Delivery;
+
+    if is_delivery && cart.total_cents() < delivery.minimum * 100 {
+        writeln!(stdout, "\n{}: Mindestbestellwert für Lieferung: {}", theme().error.paint("Bestellung abgelehnt"),
+                 format_price(delivery.minimum * 100))?;
+        wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+        return Ok(None);
+    }
+
+    // Liefergebühr als eigene Bestellzeile, damit das Backend auch den Mindestbestellwert prüft
+    let mut order = cart.order_text();
+    let mut local_prices = cart.unit_prices();
+    if is_delivery {
+        order.push_str("\ndelivery#");
+        local_prices.push(delivery.fee * 100);
+    }
+
+    let prices = match quote_order(&order, &local_prices, arguments) {
+        Ok(prices) => prices,
+        Err(text) => {
+            writeln!(stdout, "\n{}: {text}", theme().error.paint("Bestellung abgelehnt"))?;
+            wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+            return Ok(None);
+        }
+    };
+
+    // Toppings vom Lagerbestand abbuchen; ohne Backend wird trotzdem verkauft und später gebucht
+    let booking = format_stock_booking(&new_booking_key(), &order);
+    let stock_error = match send_stock_change("/stock/consume", &booking, arguments) {
+        Ok(_) => None,
+        Err(e) if e.kind() == ErrorKind::InvalidInput => {
+            writeln!(stdout, "\n{}: {e}", theme().error.paint("Bestellung abgelehnt"))?;
+            wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+            return Ok(None);
+        }
+        Err(e) => {
+            pending_consumes.push(PendingStockConsume { booking, attempts: 1 });
+            Some(e)
+        }
+    };
+
+    cart.apply_prices(&prices);
+    let fee_cents = if is_delivery { prices.last().copied().unwrap_or(0) } else { 0 };
+    let total_cents = cart.total_cents() + fee_cents;
+
+    let mut name = cart.transaction_name();
+    if is_delivery {
+        name.push_str(", Delivery fee");
+    }
+    let transaction_line = format_order_transaction_string(total_cents, &name, cart.note.as_deref(), &customer);
+
+    let (order_number, fallback, send_error) = match send_transaction_record(transaction_line.clone(), arguments) {
+        Ok(order_number) => (order_number, None, None),
+        Err(e) => (None, Some(transaction_line), Some(e)),
+    };
+
+    let receipt = Receipt::from_cart(cart, is_delivery.then_some(fee_cents), &customer, order_number, now_local_timestamp());
+    cart.clear();
+
+    clear_screen(stdout)?;
+    writeln!(stdout, "{}", receipt.to_table_menu())?;
+    writeln!(stdout, "{}.", theme().success.paint("Bestellung bestätigt"))?;
+
+    if let Some(e) = send_error {
+        writeln!(stdout, "Warnung: Konnte Transaktion nicht an Backend senden: {e}").ok();
+    }
+    if let Some(e) = stock_error {
+        writeln!(stdout, "Warnung: Lagerbestand konnte nicht gebucht werden, wird nachgeholt: {e}").ok();
+    }
+    if let Some(path) = &arguments.receipt_path
+        && let Err(e) = write_receipt(path, &receipt) {
+        writeln!(stdout, "Warnung: Bon konnte nicht nach {} geschrieben werden: {e}", path.display()).ok();
+    }
+
+    wait_enter(stdout, stdin, "\n[OK mit Enter]")?;
+    Ok(fallback)
+}
+
+fn history_menu_update(input: InputEvent, state: &mut State, stdout: &mut Stdout, stdin: &mut Stdin, arguments: &Arguments) -> bool {
+
+    let sel_row = state.selected_row();
+    let length = state.current_menu_mut().table_mut().rows_mut().len();
+
+    match input {
+        // am Seitenrand wird auf die vorige bzw. nächste Seite geblättert
+        InputEvent::Up => {
+            if sel_row > 0 {
+                state.set_selected_row(sel_row - 1);
+            } else if state.history.has_previous_page() {
+                state.set_selected_row(usize::MAX);
+                state.open_history(state.history.filter.clone(), state.history.page - 1, arguments);
+                return false;
+            }
+        }
+        InputEvent::Down => {
+            if sel_row + 1 < length {
+                state.set_selected_row(sel_row + 1);
+            } else if state.history.has_next_page() {
+                state.set_selected_row(0);
+                state.open_history(state.history.filter.clone(), state.history.page + 1, arguments);
+                return false;
+            }
+        }
+        InputEvent::Left if state.history.has_previous_page() => {
+            state.set_selected_row(0);
+            state.open_history(state.history.filter.clone(), state.history.page - 1, arguments);
+            return false;
+        }
+        InputEvent::Right if state.history.has_next_page() => {
+            state.set_selected_row(0);
+            state.open_history(state.history.filter.clone(), state.history.page + 1, arguments);
+            return false;
+        }
+        InputEvent::Enter => {
+            if let Some(entry) = state.history_entry(sel_row) {
+                let detail = entry.to_detail_menu();
+                clear_screen(stdout).ok();
+                writeln!(stdout, "{detail}").ok();
+                wait_enter(stdout, stdin, "\n[Zurück mit Enter]").ok();
+            }
+        }
+        // Filter: leere Eingabe = kein Filter
+        InputEvent::Char('f') => {
+            let input = prompt(stdin, stdout, "\nDatum (YYYY-MM-DD, YYYY-MM oder leer): ")
+                .and_then(|date| Ok((date, prompt(stdin, stdout, "Suchtext (leer = alle): ")?)));
+            match input {
+                Ok((date, text)) => {
+                    state.set_selected_row(0);
+                    state.open_history(HistoryFilter::from_input(&date, &text), 1, arguments);
+                    return false;
+                }
+                Err(e) => {
+                    writeln!(stdout, "\nFehler bei der Eingabe: {e}").ok();
+                    wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                }
+            }
+        }
+        InputEvent::Char('r') => {
+            state.set_selected_row(0);
+            state.open_history(HistoryFilter::default(), 1, arguments);
+            return false;
+        }
+        // Storno mit Grund, danach Seite neu laden
+        InputEvent::Char('c') => {
+            if let Some(entry) = state.history_entry(sel_row).cloned() {
+                if let Err(e) = cancel_order(stdout, stdin, &entry, arguments) {
+                    writeln!(stdout, "\nStorno fehlgeschlagen: {e}").ok();
+                    wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                }
+                state.open_history(state.history.filter.clone(), state.history.page, arguments);
+                return false;
+            }
+        }
+        // Umsatzbericht für den gefilterten Zeitraum
+        InputEvent::Char('s') => {
+            let report = get(&state.history.filter.report_path(), arguments)
+                .and_then(|body| parse_sales_report(&body)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)));
+            clear_screen(stdout).ok();
+            match report {
+                Ok(days) => writeln!(stdout, "{}", sales_report_menu(&days, &state.history.filter)).ok(),
+                Err(e) => writeln!(stdout, "Fehler beim Laden des Umsatzberichts: {e}").ok(),
+            };
+            wait_enter(stdout, stdin, "\n[Zurück mit Enter]").ok();
+        }
+        InputEvent::Back => {
+            state.current_menu = MenuIndex::MainMenu;
+            state.apply_selection_marker();
+            return false;
+        }
+        _ => {}
+    }
+
+    state.apply_selection_marker();
+
+    false
+}
+
+fn cancel_order(stdout: &mut Stdout, stdin: &mut Stdin, entry: &HistoryEntry, arguments: &Arguments) -> Result<(), Box<dyn Error>> {
+    if let Some(reason) = entry.refund_blocker() {
+        writeln!(stdout, "\n{reason}.")?;
+        wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+        return Ok(());
+    }
+
+    let question = theme().confirm_question(&format!("Bestellung #{} ({}) stornieren?", entry.number, entry.price_text()));
+    if !confirm(stdin, stdout, &question)? {
+        return Ok(());
+    }
+
+    let reason = prompt(stdin, stdout, "Grund: ")?;
+    if reason.is_empty() {
+        writeln!(stdout, "Ohne Grund wird nicht storniert.")?;
+        wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+        return Ok(());
+    }
+
+    match send_refund(entry.number, &reason, arguments)? {
+        Some(refund) => writeln!(stdout, "Storno #{refund} gebucht.")?,
+        None => writeln!(stdout, "Storno gebucht.")?,
+    }
+    wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+    Ok(())
+}
+
 fn main_menu_update(input: InputEvent, state: &mut State, arguments: &Arguments) -> bool {
 
     match input {
@@ -128,10 +674,14 @@ fn main_menu_update(input: InputEvent, state: &mut State, arguments: &Arguments)
                     state.apply_selection_marker();
                 },
                 1 => {
+                    state.selected_rows[MenuIndex::HistoryMenu.as_index()] = 0;
+                    state.open_history(HistoryFilter::default(), 1, arguments);
+                },
+                2 => {
                     state.current_menu = MenuIndex::EditToppingsMenu;
                     state.apply_selection_marker();
                 },
-                2 => return true,
+                3 => return true,
                 _ => todo!()
             }
         }
@@ -186,20 +736,20 @@ fn edit_toppings_menu_update(input: InputEvent, state: &mut State, stdout: &mut
                 }
                 2 => {
                     let _ = clear_screen(stdout);
-                    if let Err(e) = list_toppings_from_backend(stdout, arguments) {
+                    if let Err(e) = browse_toppings(stdout, stdin, arguments) {
                         writeln!(stdout, "Fehler {e}").ok();
+                        wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
                     }
-                    wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
                 }
                 3 => {
                     let _ = clear_screen(stdout);
 
-                    match confirm(stdin, stdout, "\n\x1b[34mListe wirklich löschen?\x1b[0m (\x1b[32mY\x1b[0m/\x1b[31mN\x1b[0m): ") {
+                    match confirm(stdin, stdout, &theme().confirm_question("Liste wirklich löschen?")) {
                         Ok(true) => {
                             if let Err(e) = send_clear_toppings("/toppings/clear", arguments) {
                                 writeln!(stdout, "Fehler: {e}").ok();
                             } else {
-                                writeln!(stdout, "\x1b[1;35mDatei geleert. \x1b[0m").ok();
+                                writeln!(stdout, "{}", theme().notice.paint("Datei geleert.")).ok();
                             }
                         }
                         Ok(false) => {
@@ -211,6 +761,13 @@ fn edit_toppings_menu_update(input: InputEvent, state: &mut State, stdout: &mut
                     }
                     wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
                 }
+                4 => {
+                    let _ = clear_screen(stdout);
+                    if let Err(e) = restock_topping(stdout, stdin, arguments) {
+                        writeln!(stdout, "Fehler: {e}").ok();
+                    }
+                    wait_enter(stdout, stdin, "\n[Weiter mit Enter]").ok();
+                }
                 _ => {}
             }
         }
@@ -239,16 +796,36 @@ pub fn select_row(table: &mut Table, selected_row: usize) {
     }
 }
 
-pub fn order_custom_pizza(stdout: &mut Stdout, stdin:  &mut Stdin, available_toppings: &[Topping], base_price: u32, arguments: &Arguments) -> Result<Option<String>, Box<dyn Error>> {
+//ohne Vorlage: Custom-Pizza von Grund auf; mit Vorlage: angepasste Prebuilt-Pizza (Toppings vorbelegt)
+pub fn order_custom_pizza(stdout: &mut Stdout, stdin:  &mut Stdin, available_toppings: &[Topping], template: Option<&Pizza>, rule: Option<&PricingRule>, limits: &ToppingLimits, arguments: &Arguments) -> Result<Option<CartEntry>, Box<dyn Error>> {
     let mut selected_row: usize = 0;
     let n = available_toppings.len();
     let checkout_row = n;
     let clear_row    = n + 1;
     let back_row     = n + 2;
 
+    let (title, base_price, scope) = match template {
+        Some(pizza) => (format!("Customize {}", pizza.name), pizza.base_price, PricingScope::Prebuilt),
+        None => (String::from("Custom Pizza"), CUSTOM_BASE_PRICE, PricingScope::Custom),
+    };
+
     // Menge je Topping (für Mehrfachauswahl)
-    let mut quantity = vec![0u32; n];
-    let mut in_buf = [0u8; 64];
+    let mut quantity = match template {
+        Some(pizza) => prebuilt_quantities(pizza, available_toppings),
+        None => vec![0u32; n],
+    };
+    // Lage je Topping (ganze Pizza oder eine Hälfte)
+    let mut placement = vec![Placement::Whole; n];
+    // Scroll-Position bleibt über das Neuzeichnen hinweg erhalten
+    let mut offset: usize = 0;
+    let mut filter: Option<String> = None;
+    let mut message: Option<String> = None;
+    // Hotkeys in Reihenfolge der Auswahlindizes: Toppings, dann die Aktionen
+    let labels: Vec<&str> = available_toppings.iter().map(|t| t.name.as_str())
+        .chain(["Add to cart", "Clear selection", "Back"])
+        .collect();
+    let keys = assign_hotkeys(&labels, &[]);
+    let key_label = |row: usize| keys[row].map(|key| format!("{key}:")).unwrap_or_default();
 
     loop {
         // Render
@@ -258,14 +835,19 @@ pub fn order_custom_pizza(stdout: &mut Stdout, stdin:  &mut Stdin, available_top
         for (i, t) in available_toppings.iter().enumerate() {
             let marker = if i == selected_row { ">" } else { " " };
             let qty_str = if quantity[i] > 0 { format!("x{}", quantity[i]) } else { String::new() };
+            let placement_str = if quantity[i] > 0 || placement[i] != Placement::Whole { placement[i].label() } else { "" };
 
             table.push(TableRow::new(vec![
                 TableCell::new(marker.into()),
-                TableCell::new(format!("{}:", i + 1)),
+                TableCell::new(key_label(i)),
                 TableCell::new(t.name.clone()),
                 TableCell::new_with_alignment(format!("{}.00$", t.price), Right),
                 TableCell::new_with_alignment(format!(" {qty_str}"), Right),
-            ]));
+                TableCell::new(format!(" {placement_str}")),
+                TableCell::new(t.info.diet_label().into()),
+                TableCell::new(t.info.allergen_label()),
+                TableCell::new(if t.is_sold_out() { "sold out" } else { "" }.into()),
+            ]).with_style(if t.is_sold_out() { theme().disabled } else { Style::plain() }));
         }
 
         table.push(TableRow::new(vec![
@@ -274,52 +856,127 @@ pub fn order_custom_pizza(stdout: &mut Stdout, stdin:  &mut Stdin, available_top
             TableCell::new(String::new()),
             TableCell::new_with_alignment(String::new(), Right),
             TableCell::new_with_alignment(String::new(), Right),
+            TableCell::new(String::new()),
+            TableCell::new(String::new()),
+            TableCell::new(String::new()),
+            TableCell::new(String::new()),
         ]));
 
         // Aktionen
-        let make_action = |idx: usize, tag: &str, label: &str| {
+        let make_action = |idx: usize, label: &str| {
             TableRow::new(vec![
                 TableCell::new(if selected_row == idx { ">" } else { " " }.into()), // Marker
-                TableCell::new(tag.into()),                                         // Icon/Index
+                TableCell::new(key_label(idx)),                                     // Hotkey
                 TableCell::new(label.into()),                                       // Text
                 TableCell::new_with_alignment(String::new(), Right),         // Preis-Platzhalter
                 TableCell::new_with_alignment(String::new(), Right),         // Menge-Platzhalter
+                TableCell::new(String::new()),                                      // Lage-Platzhalter
+                TableCell::new(String::new()),                                      // Ernährung-Platzhalter
+                TableCell::new(String::new()),                                      // Allergene-Platzhalter
+                TableCell::new(String::new()),                                      // Bestand-Platzhalter
             ])
         };
 
-        // Aktionen (mit 5 Spalten!)
-        table.push(make_action(checkout_row, "C", "Checkout"));
-        table.push(make_action(clear_row,    "CL", "Clear selection"));
-        table.push(make_action(back_row,     "B", "Back"));
+        // Aktionen (mit 9 Spalten!)
+        table.push(make_action(checkout_row, labels[checkout_row]));
+        table.push(make_action(clear_row,    labels[clear_row]));
+        table.push(make_action(back_row,     labels[back_row]));
 
         // Menütitel + Ausgabe
-        let tm = TableMenu::new("Custom Pizza".into(), table);
+        let mut tm = TableMenu::new(title.clone(), table);
+        tm.set_offset(offset);
+        tm.set_filter(filter.clone());
+
+        let toppings_sum: u32 = quantity.iter().enumerate().map(|(i, &q)| q * placement[i].price_cents(available_toppings[i].price)).sum();
+        let total = discounted_cents(base_price * 100 + toppings_sum, scope, rule);
+        let discount = match rule {
+            Some(rule) if rule.covers(scope) => format!(" ({})", rule.label()),
+            _ => String::new(),
+        };
 
-        let toppings_sum: u32 = quantity.iter().enumerate().map(|(i, &q)| q * available_toppings[i].price).sum();
-        let total = base_price + toppings_sum;
+        let topping_count: u32 = quantity.iter().sum();
+        // Ernährungsinfo der aktuellen Auswahl
+        let info = FoodInfo::combine(
+            &pizza_base_info(),
+            available_toppings.iter().zip(&quantity).filter(|(_, q)| **q > 0).map(|(t, _)| &t.info),
+        );
+        let info_line = match info.diet_label() {
+            "" => format!("Allergene: {}", info.allergen_label()),
+            diet => format!("{diet} | Allergene: {}", info.allergen_label()),
+        };
+        let message_line = match message.take() {
+            Some(text) => theme().error.paint(&text),
+            None => String::new(),
+        };
 
         let footer = [
             "",
-            &format!("Basispreis: {}.00$ | Toppings: {}.00$ | Gesamt: \x1b[1m{}.00$\x1b[0m",
-                        base_price, toppings_sum, total),
-            "[↑/↓] bewegen · [Enter] hinzufügen/auswählen · [←] entfernen · [Backspace] zurück",
+            &format!("Basispreis: {}.00$ | Toppings: {} | Gesamt: {}{}",
+                        base_price, format_price(toppings_sum), theme().emphasis.paint(&format_price(total)), discount),
+            &format!("Toppings: {}/{}", topping_count, limits.max_total),
+            &info_line,
+            &message_line,
+            "[↑/↓] bewegen · [Bild↑/Bild↓] Seite · [Enter] hinzufügen/auswählen · [-] entfernen · [←/→] linke/rechte Hälfte · [/] + Text suchen · [Backspace] zurück",
         ];
-        render_menu(stdout, &tm, "CustomPizza", selected_row, &footer)?;
+        render_menu(stdout, &mut tm, "CustomPizza", selected_row, &footer)?;
         stdout.flush()?;
+        offset = tm.offset();
 
         // Eingabe
-        let ev = read_input(stdin, &mut in_buf)?;
+        let ev = read_input(stdin)?;
+        let filter_changed = edit_filter(&mut filter, &ev);
+        tm.set_filter(filter.clone());
+        // Tabellenzeile -> Auswahlindex (Leerzeile vor den Aktionen hat keinen)
+        let selection = |row: usize| (row != n).then(|| if row > n { row - 1 } else { row });
+        let rows: Vec<usize> = tm.visible_rows().into_iter().filter_map(selection).collect();
+        if filter_changed {
+            selected_row = snap_row(&rows, selected_row);
+            continue;
+        }
+        if filter.is_some() && rows.is_empty() {
+            continue;
+        }
+        // Hotkey oder Klick wählt die Zeile aus und löst sie aus wie Enter
+        let ev = match ev {
+            InputEvent::Char(c) => match keys.iter().position(|key| *key == Some(c.to_ascii_uppercase())) {
+                Some(row) => {
+                    selected_row = row;
+                    InputEvent::Enter
+                }
+                None => ev,
+            },
+            InputEvent::Mouse(mouse) => match mouse.kind {
+                MouseKind::WheelUp | MouseKind::WheelDown => {
+                    selected_row = page_row(&rows, selected_row, 1, mouse.kind == MouseKind::WheelDown);
+                    continue;
+                }
+                MouseKind::Press(MouseButton::Left) => match tm.row_at(mouse.column, mouse.row).and_then(selection) {
+                    Some(row) => {
+                        selected_row = row;
+                        InputEvent::Enter
+                    }
+                    None => continue,
+                },
+                _ => continue,
+            },
+            _ => ev,
+        };
+
         match ev {
-            InputEvent::Up => {
-                if selected_row > 0 { selected_row -= 1; } else { selected_row = back_row; }
+            InputEvent::Up | InputEvent::Down => {
+                selected_row = step_row(&rows, selected_row, matches!(ev, InputEvent::Down));
             }
-            InputEvent::Down => {
-                if selected_row < back_row { selected_row += 1; } else { selected_row = 0; }
+            InputEvent::PageUp | InputEvent::PageDown => {
+                selected_row = page_row(&rows, selected_row, tm.page_size(), matches!(ev, InputEvent::PageDown));
             }
-            InputEvent::Left => {
-                if selected_row < n && quantity[selected_row] > 0 {
-                    quantity[selected_row] -= 1;
-                }
+            InputEvent::Left if selected_row < n => {
+                placement[selected_row] = placement[selected_row].move_left();
+            }
+            InputEvent::Right if selected_row < n => {
+                placement[selected_row] = placement[selected_row].move_right();
+            }
+            InputEvent::Char('-') if selected_row < n && quantity[selected_row] > 0 => {
+                quantity[selected_row] -= 1;
             }
             InputEvent::Back => {
                 // Abbruch zurück zum Order-Menü
@@ -327,51 +984,69 @@ pub fn order_custom_pizza(stdout: &mut Stdout, stdin:  &mut Stdin, available_top
             }
             InputEvent::Enter => {
                 if selected_row < n {
-                    // topping hinzufügen
-                    quantity[selected_row] += 1;
+                    // topping hinzufügen, sofern Bestand und Limits es erlauben
+                    let topping = &available_toppings[selected_row];
+                    let allowed = topping.check_stock(quantity[selected_row])
+                        .and_then(|()| limits.check_add(&topping.name, quantity[selected_row], topping_count));
+                    match allowed {
+                        Ok(()) => quantity[selected_row] += 1,
+                        Err(text) => message = Some(text),
+                    }
                 } else if selected_row == checkout_row {
-                    // Checkout: Zusammenfassung + Preis anzeigen
+                    if let Err(text) = limits.check_pizza(available_toppings, &quantity, template.is_some())
+                        .and_then(|()| check_stock(available_toppings, &quantity)) {
+                        message = Some(text);
+                        continue;
+                    }
+
+                    let local_cents = discounted_cents(
+                        calc_custom_total_cents(base_price, available_toppings, &quantity, &placement),
+                        scope,
+                        rule
+                    );
+                    let order_line = match template {
+                        Some(pizza) => format_customized_order_line(pizza, available_toppings, &quantity, &placement),
+                        None => format_custom_order_line(available_toppings, &quantity, &placement),
+                    };
+                    let price_cents = match quote_order_line(
+                        &order_line,
+                        local_cents,
+                        arguments
+                    ) {
+                        Ok(cents) => cents,
+                        Err(text) => {
+                            message = Some(format!("Bestellung abgelehnt: {text}"));
+                            continue;
+                        }
+                    };
+
+                    // Zusammenfassung + Preis anzeigen
                     clear_screen(stdout)?;
                     let mut sum_table = Table::new(vec![]);
                     for (i, &q) in quantity.iter().enumerate().filter(|(_, q)| **q > 0) {
+                        let half = if placement[i] == Placement::Whole { String::new() } else { format!(" ({})", placement[i].label()) };
                         sum_table.push(TableRow::new(vec![
-                            TableCell::new(format!("{} x {}", available_toppings[i].name, q)),
-                            TableCell::new_with_alignment(format!("{}.00$", available_toppings[i].price * q), Right),
+                            TableCell::new(format!("{} x {}{}", available_toppings[i].name, q, half)),
+                            TableCell::new_with_alignment(format_price(placement[i].price_cents(available_toppings[i].price) * q), Right),
                         ]));
                     }
                     let tm2 = TableMenu::new("Your toppings".into(), sum_table);
                     writeln!(stdout, "{tm2}")?;
-                    let pizza = Pizza {
-                        name: "Custom".into(),
-                        base_price,
-                        toppings: {
-                            let mut v = Vec::new();
-                            for (i, &q) in quantity.iter().enumerate() {
-                                for _ in 0..q { v.push(available_toppings[i].clone()); }
-                            }
-                            v
-                        },
-                    };
-                    writeln!(stdout, "Gesamtpreis: \x1b[4;30m{}.00$\x1b[0m", pizza.total_price())?;
-                    let transaction_line = format_custom_pizza_as_transaction_string(
-                        base_price,
-                        available_toppings,
-                        &quantity,
-                        true
-                    );
-
-                    if let Err(e) = send_transaction_record(transaction_line.clone(), arguments) {
-                        writeln!(stdout, "Warnung: Konnte Transaktion nicht an Backend senden: {e}").ok();
-                        wait_enter(stdout, stdin, "\n[OK mit Enter]")?;
-                        return Ok(Some(transaction_line));
-                    }
-
+                    writeln!(stdout, "Preis: {}", theme().price.paint(&format_price(price_cents)))?;
+                    writeln!(stdout, "\n{}.", theme().success.paint("In den Warenkorb gelegt"))?;
                     wait_enter(stdout, stdin, "\n[OK mit Enter]")?;
-                    return Ok(None);
+
+                    let name = match template {
+                        Some(pizza) => build_customized_name(pizza, available_toppings, &quantity, &placement),
+                        None => build_custom_name(available_toppings, &quantity, &placement, true),
+                    };
+                    let toppings = describe_toppings(available_toppings, &quantity, &placement);
+                    return Ok(Some(CartEntry::new(name, order_line, price_cents).with_details(toppings)));
 
                 } else if selected_row == clear_row {
                     // Auswahl zurücksetzen
-                    quantity.fill(0)
+                    quantity.fill(0);
+                    placement.fill(Placement::Whole);
                 } else {
                     // Back
                     return Ok(None);
@@ -381,3 +1056,112 @@ pub fn order_custom_pizza(stdout: &mut Stdout, stdin:  &mut Stdin, available_top
         }
     }
 }
+
+pub fn order_bundle(stdout: &mut Stdout, stdin: &mut Stdin, bundle: &Bundle, arguments: &Arguments) -> Result<Option<CartEntry>, Box<dyn Error>> {
+    let mut choices: Vec<String> = Vec::new();
+    let mut selected_row: usize = 0;
+
+    // Slots der Reihe nach befüllen
+    while choices.len() < bundle.slots.len() {
+        let slot_index = choices.len();
+        let slot = &bundle.slots[slot_index];
+
+        let mut table = Table::new(vec![]);
+        for (i, item) in slot.allowed.iter().enumerate() {
+            table.push(TableRow::new(vec![
+                TableCell::new(if i == selected_row { ">" } else { " " }.into()),
+                TableCell::new(format!("{}:", i + 1)),
+                TableCell::new(item.clone()),
+            ]));
+        }
+        if slot.allowed.is_empty() {
+            table.push(TableRow::new(vec![
+                TableCell::new(" ".into()),
+                TableCell::new("-".into()),
+                TableCell::new("Keine Auswahl möglich".into()),
+            ]));
+        }
+
+        let title = format!("{} - {} ({}/{})", bundle.name, slot.label, slot_index + 1, bundle.slots.len());
+        let mut tm = TableMenu::new(title, table);
+
+        let chosen = if choices.is_empty() { String::from("-") } else { choices.join(", ") };
+        let footer = [
+            "",
+            &format!("Bisher: {chosen} | Bundle-Preis: {}", theme().emphasis.paint(&format_price(bundle.price * 100))),
+            "[↑/↓] bewegen · [Enter] auswählen · [Backspace] vorheriger Slot/zurück",
+        ];
+        render_menu(stdout, &mut tm, "Bundle", selected_row, &footer)?;
+        stdout.flush()?;
+
+        let length = slot.allowed.len();
+        let input = match read_input(stdin)? {
+            // Klick wählt den Eintrag direkt
+            InputEvent::Mouse(mouse) => match mouse.kind {
+                MouseKind::WheelUp => InputEvent::Up,
+                MouseKind::WheelDown => InputEvent::Down,
+                MouseKind::Press(MouseButton::Left) => match tm.row_at(mouse.column, mouse.row) {
+                    Some(row) => {
+                        selected_row = row;
+                        InputEvent::Enter
+                    }
+                    None => continue,
+                },
+                _ => continue,
+            },
+            input => input,
+        };
+        match input {
+            InputEvent::Up => {
+                if selected_row > 0 { selected_row -= 1; } else { selected_row = length.saturating_sub(1); }
+            }
+            InputEvent::Down => {
+                if selected_row + 1 < length { selected_row += 1; } else { selected_row = 0; }
+            }
+            InputEvent::Enter => {
+                if let Some(item) = slot.allowed.get(selected_row) {
+                    choices.push(item.clone());
+                    selected_row = 0;
+                }
+            }
+            InputEvent::Back => {
+                if choices.pop().is_none() {
+                    return Ok(None);
+                }
+                selected_row = 0;
+            }
+            InputEvent::Exit => return Ok(None),
+            _ => {}
+        }
+    }
+
+    // Zusammenfassung + Preis
+    clear_screen(stdout)?;
+    let mut sum_table = Table::new(vec![]);
+    for (slot, choice) in bundle.slots.iter().zip(choices.iter()) {
+        sum_table.push(TableRow::new(vec![
+            TableCell::new(format!("{}:", slot.label)),
+            TableCell::new(choice.clone()),
+        ]));
+    }
+    writeln!(stdout, "{}", TableMenu::new(bundle.name.clone(), sum_table))?;
+
+    let order_line = format_bundle_order_line(bundle, &choices);
+    let price_cents = match quote_order_line(&order_line, bundle.price * 100, arguments) {
+        Ok(cents) => cents,
+        Err(text) => {
+            writeln!(stdout, "{}: {text}", theme().error.paint("Bestellung abgelehnt"))?;
+            wait_enter(stdout, stdin, "\n[Weiter mit Enter]")?;
+            return Ok(None);
+        }
+    };
+    writeln!(stdout, "Preis: {}", theme().price.paint(&format_price(price_cents)))?;
+    writeln!(stdout, "\n{}.", theme().success.paint("In den Warenkorb gelegt"))?;
+    wait_enter(stdout, stdin, "\n[OK mit Enter]")?;
+
+    let details = bundle.slots.iter()
+        .zip(choices.iter())
+        .map(|(slot, choice)| format!("{}: {}", slot.label, choice))
+        .collect();
+    Ok(Some(CartEntry::new(build_bundle_name(bundle, &choices), order_line, price_cents).with_details(details)))
+}
@@ -0,0 +1,148 @@
+//Anzeigebreite im Terminal: ANSI-Sequenzen und kombinierende Zeichen haben Breite 0,
+//ostasiatische Schriftzeichen und Emoji Breite 2 (Tabellen, Menü-Boxen, fit_box)
+
+fn is_zero_width(c: char) -> bool {
+    matches!(c as u32,
+        0x0300..=0x036F     // kombinierende diakritische Zeichen
+        | 0x0483..=0x0489
+        | 0x0591..=0x05BD
+        | 0x0E31 | 0x0E34..=0x0E3A | 0x0E47..=0x0E4E
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F   // Zero Width Space/Joiner, Richtungszeichen
+        | 0x2028..=0x202E
+        | 0x2060..=0x2064
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F   // Variation Selectors
+        | 0xFE20..=0xFE2F
+        | 0xFEFF
+        | 0x1F3FB..=0x1F3FF // Hautfarben-Modifikatoren
+        | 0xE0000..=0xE007F
+        | 0xE0100..=0xE01EF
+    ) || c.is_control()
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F     // Hangul Jamo
+        | 0x231A..=0x231B | 0x23E9..=0x23EC | 0x23F0 | 0x23F3
+        | 0x25FD..=0x25FE
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x267F | 0x2693 | 0x26A1 | 0x26AA..=0x26AB | 0x26BD..=0x26BE
+        | 0x26C4..=0x26C5 | 0x26CE | 0x26D4 | 0x26EA | 0x26F2..=0x26F3
+        | 0x26F5 | 0x26FA | 0x26FD | 0x2705 | 0x270A..=0x270B | 0x2728
+        | 0x274C | 0x274E | 0x2753..=0x2755 | 0x2757 | 0x2795..=0x2797
+        | 0x27B0 | 0x27BF | 0x2B1B..=0x2B1C | 0x2B50 | 0x2B55
+        | 0x2E80..=0x303E   // CJK-Radikale, Satzzeichen
+        | 0x3041..=0x33FF   // Hiragana, Katakana, …
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF   // CJK-Ideogramme
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3   // Hangul-Silben
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60   // Vollbreite Formen
+        | 0xFFE0..=0xFFE6
+        | 0x1F004 | 0x1F0CF | 0x1F18E | 0x1F191..=0x1F19A
+        | 0x1F200..=0x1F251
+        | 0x1F300..=0x1F64F // Emoji
+        | 0x1F680..=0x1F6FF
+        | 0x1F7E0..=0x1F7EB
+        | 0x1F90C..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x20000..=0x3FFFD
+    )
+}
+
+//Breite eines Zeichens ohne Berücksichtigung von ANSI-Sequenzen
+pub fn char_width(c: char) -> usize {
+    if is_zero_width(c) {
+        0
+    } else if is_wide(c) {
+        2
+    } else {
+        1
+    }
+}
+
+//zerlegt in (Teilstring, Breite); Escape-Sequenzen und Zeichen hinter einem ZWJ
+//(Emoji-Sequenzen wie 👨‍🍳) zählen nicht
+fn segments(text: &str) -> Vec<(&str, usize)> {
+    let mut segments = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut after_joiner = false;
+
+    while let Some((start, c)) = chars.next() {
+        if c == '\x1b' {
+            let mut end = start + 1;
+            if let Some(&(_, '[')) = chars.peek() {
+                chars.next();
+                end += 1;
+                // CSI: Parameter bis zum abschließenden Buchstaben (0x40..=0x7E)
+                for (index, c) in chars.by_ref() {
+                    end = index + c.len_utf8();
+                    if ('\x40'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            segments.push((&text[start..end], 0));
+            continue;
+        }
+
+        let width = if after_joiner { 0 } else { char_width(c) };
+        after_joiner = c == '\u{200D}';
+        segments.push((&text[start..start + c.len_utf8()], width));
+    }
+
+    segments
+}
+
+pub fn display_width(text: &str) -> usize {
+    segments(text).iter().map(|(_, width)| width).sum()
+}
+
+//schneidet auf höchstens `width` Spalten ab; Escape-Sequenzen bleiben erhalten
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    let mut used = 0;
+    let mut truncated = String::new();
+    for (segment, segment_width) in segments(text) {
+        if used + segment_width > width {
+            // auch nachfolgende kombinierende Zeichen gehören zum abgeschnittenen Zeichen
+            break;
+        }
+        used += segment_width;
+        truncated.push_str(segment);
+    }
+    truncated
+}
+
+//rechts mit Leerzeichen auf `width` Spalten auffüllen
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    format!("{text}{}", " ".repeat(width.saturating_sub(display_width(text))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("Ham"), 3);
+        assert_eq!(display_width("Käse"), 4);
+        assert_eq!(display_width("Jalapen\u{0303}o"), 8);
+        assert_eq!(display_width("寿司"), 4);
+        assert_eq!(display_width("🍕 Pizza"), 8);
+        assert_eq!(display_width("👨\u{200D}🍳"), 2);
+        assert_eq!(display_width("\x1b[1;33mLager\x1b[0m"), 5);
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("Jalapeño", 5), "Jalap");
+        assert_eq!(truncate_to_width("寿司ピザ", 5), "寿司");
+        assert_eq!(truncate_to_width("\x1b[1mKäse\x1b[0m", 2), "\x1b[1mKä");
+        assert_eq!(pad_to_width("Käse", 6), "Käse  ");
+    }
+}
@@ -0,0 +1,44 @@
+//Liefergebühr und Mindestbestellwert in Euro (delivery_text, GET /delivery)
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct DeliverySettings {
+    pub fee: u32,
+    pub minimum: u32,
+}
+
+// Format: fee#<Euro> und minimum#<Euro>, fehlende Einträge = 0
+pub fn parse_delivery_settings(content: &str) -> Result<DeliverySettings, String> {
+    let mut settings = DeliverySettings::default();
+
+    for (lineno, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value_text) = line.split_once('#')
+            .ok_or_else(|| format!("Zeile {}: erwartet 'Schlüssel#Euro'", lineno + 1))?;
+        let value = value_text.trim().parse::<u32>()
+            .map_err(|_| format!("Zeile {}: Ungültiger Betrag '{}'", lineno + 1, value_text.trim()))?;
+
+        match key.trim() {
+            "fee" => settings.fee = value,
+            "minimum" => settings.minimum = value,
+            other => return Err(format!("Zeile {}: Unbekannter Schlüssel '{}'", lineno + 1, other)),
+        }
+    }
+
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_delivery_settings() {
+        assert_eq!(parse_delivery_settings("fee#3\nminimum#15\n"), Ok(DeliverySettings { fee: 3, minimum: 15 }));
+        assert_eq!(parse_delivery_settings(""), Ok(DeliverySettings::default()));
+        assert!(parse_delivery_settings("fee#drei").is_err());
+        assert!(parse_delivery_settings("tip#2").is_err());
+    }
+}
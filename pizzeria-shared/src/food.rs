@@ -0,0 +1,165 @@
+//Ernährungs- und Allergenkennzeichnungen im dritten Topping-Feld, z.B. "Corn#4#vegan"
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Allergen {
+    Gluten,
+    Lactose,
+    Nuts,
+    Egg,
+    Fish,
+    Soy,
+    Celery,
+    Mustard,
+}
+
+impl Allergen {
+    pub const ALL: [Allergen; 8] = [
+        Allergen::Gluten, Allergen::Lactose, Allergen::Nuts, Allergen::Egg,
+        Allergen::Fish, Allergen::Soy, Allergen::Celery, Allergen::Mustard,
+    ];
+
+    //Kennung im Topping-Format
+    pub fn code(&self) -> &'static str {
+        match self {
+            Allergen::Gluten => "gluten",
+            Allergen::Lactose => "lactose",
+            Allergen::Nuts => "nuts",
+            Allergen::Egg => "egg",
+            Allergen::Fish => "fish",
+            Allergen::Soy => "soy",
+            Allergen::Celery => "celery",
+            Allergen::Mustard => "mustard",
+        }
+    }
+
+    //Kürzel für die Menüspalte
+    pub fn short(&self) -> &'static str {
+        match self {
+            Allergen::Gluten => "Gl",
+            Allergen::Lactose => "La",
+            Allergen::Nuts => "Nu",
+            Allergen::Egg => "Ei",
+            Allergen::Fish => "Fi",
+            Allergen::Soy => "So",
+            Allergen::Celery => "Se",
+            Allergen::Mustard => "Mu",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DietaryFlag {
+    Vegetarian,
+    Vegan,
+}
+
+impl DietaryFlag {
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DietaryFlag::Vegetarian => "vegetarian",
+            DietaryFlag::Vegan => "vegan",
+        }
+    }
+
+    //Filter im Order-Menü: aus -> vegetarian -> vegan -> aus
+    pub fn next_filter(filter: Option<DietaryFlag>) -> Option<DietaryFlag> {
+        match filter {
+            None => Some(DietaryFlag::Vegetarian),
+            Some(DietaryFlag::Vegetarian) => Some(DietaryFlag::Vegan),
+            Some(DietaryFlag::Vegan) => None,
+        }
+    }
+}
+
+//Ernährungs- und Allergeninfo; ohne Angaben gilt ein Topping als nicht vegetarisch
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct FoodInfo {
+    pub vegetarian: bool,
+    pub vegan: bool,
+    pub allergens: Vec<Allergen>,
+}
+
+impl FoodInfo {
+
+    // Format: Kennungen mit '|' getrennt, z.B. "vegetarian|lactose"; vegan schließt vegetarian ein
+    pub fn parse(tags: &str) -> Result<FoodInfo, String> {
+        let mut info = FoodInfo::default();
+
+        for tag in tags.split('|').map(str::trim).filter(|tag| !tag.is_empty()) {
+            match tag.to_ascii_lowercase().as_str() {
+                "vegetarian" => info.vegetarian = true,
+                "vegan" => {
+                    info.vegetarian = true;
+                    info.vegan = true;
+                }
+                code => match Allergen::ALL.iter().find(|a| a.code() == code) {
+                    Some(allergen) => info.add_allergen(*allergen),
+                    None => return Err(format!("Unbekannte Kennzeichnung '{tag}'")),
+                },
+            }
+        }
+
+        Ok(info)
+    }
+
+    pub fn to_tags(&self) -> String {
+        let diet = if self.vegan {
+            Some("vegan")
+        } else if self.vegetarian {
+            Some("vegetarian")
+        } else {
+            None
+        };
+        diet.into_iter()
+            .chain(self.allergens.iter().map(Allergen::code))
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
+    fn add_allergen(&mut self, allergen: Allergen) {
+        if !self.allergens.contains(&allergen) {
+            self.allergens.push(allergen);
+        }
+    }
+
+    pub fn has(&self, flag: DietaryFlag) -> bool {
+        match flag {
+            DietaryFlag::Vegetarian => self.vegetarian,
+            DietaryFlag::Vegan => self.vegan,
+        }
+    }
+
+    //vegetarisch/vegan nur, wenn alle Teile es sind; Allergene werden vereinigt
+    pub fn combine<'a>(base: &FoodInfo, parts: impl IntoIterator<Item = &'a FoodInfo>) -> FoodInfo {
+        let mut info = base.clone();
+        for part in parts {
+            info.vegetarian &= part.vegetarian;
+            info.vegan &= part.vegan;
+            for allergen in &part.allergens {
+                info.add_allergen(*allergen);
+            }
+        }
+        info.allergens.sort_by_key(|a| Allergen::ALL.iter().position(|x| x == a));
+        info
+    }
+
+    //Spalte im Menü, z.B. "vegan" oder "vegetarian"
+    pub fn diet_label(&self) -> &'static str {
+        if self.vegan {
+            DietaryFlag::Vegan.label()
+        } else if self.vegetarian {
+            DietaryFlag::Vegetarian.label()
+        } else {
+            ""
+        }
+    }
+
+    //Spalte im Menü, z.B. "Gl,La"
+    pub fn allergen_label(&self) -> String {
+        self.allergens.iter()
+            .map(Allergen::short)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
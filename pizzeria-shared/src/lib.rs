@@ -0,0 +1,4 @@
+//Dateiformate, die Frontend und Backend gleich lesen müssen
+pub mod delivery;
+pub mod food;
+pub mod transaction_log;
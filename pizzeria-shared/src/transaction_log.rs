@@ -0,0 +1,120 @@
+//Transaktionszeilen in transactions.log: Frontend schreibt, Backend liest und bucht Stornos
+use std::ffi::CStr;
+use std::ptr;
+use libc::{localtime_r, strftime, time, time_t, tm};
+
+//Zeitstempel über libc
+pub fn now_local_timestamp() -> String {
+    unsafe {
+        let now_time: time_t = time(ptr::null_mut());
+        let mut local_time: tm = std::mem::zeroed();
+
+        if localtime_r(&now_time, &mut local_time).is_null() {
+            return String::from("1970-01-01 00:00:00");     //Fallback
+        }
+
+        //"YYYY-MM-DD HH:MM:SS" -> max 19 + NUL
+        let mut buf = [0u8; 32];
+        let format = b"%Y-%m-%d %H:%M:%S\0";
+
+        let written = strftime(
+            buf.as_mut_ptr() as *mut _,
+            buf.len(),
+            format.as_ptr() as *const _,
+            &local_time as *const tm,
+        );
+
+        if written == 0 {
+            return String::from("1970-01-01 00:00:00");     //Fallback
+        }
+
+        // C-String -> Rust-String
+        let cstr = CStr::from_ptr(buf.as_ptr() as *const i8);
+        cstr.to_string_lossy().into_owned()
+
+    }
+}
+
+//';' trennt die Felder einer Transaktionszeile, daher '\', ';' und Zeilenumbrüche maskieren
+pub fn escape_transaction_field(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+//Gegenstück zu escape_transaction_field: trennt an unmaskierten ';'
+pub fn split_transaction_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => field.push('\n'),
+                Some('r') => field.push('\r'),
+                Some(other) => field.push(other),
+                None => field.push('\\'),
+            },
+            ';' => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+//Positionen einer Sammel-Transaktion, getrennt durch ", ". Bundles und angepasste Pizzen
+//enthalten selbst ", ", daher in den Positionen '\' und ',' maskieren
+pub fn join_transaction_items<'a>(items: impl IntoIterator<Item = &'a str>) -> String {
+    items.into_iter()
+        .map(|item| item.replace('\\', "\\\\").replace(',', "\\,"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+//Gegenstück zu join_transaction_items: trennt an unmaskierten ','
+pub fn split_transaction_items(name: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut chars = name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => item.push(chars.next().unwrap_or('\\')),
+            ',' => {
+                chars.next_if_eq(&' ');
+                items.push(std::mem::take(&mut item));
+            }
+            c => item.push(c),
+        }
+    }
+    items.push(item);
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_transaction_fields() {
+        assert_eq!(split_transaction_fields("a;b\\;c;d\\\\;e\\nf"), vec!["a", "b;c", "d\\", "e\nf"]);
+        assert_eq!(split_transaction_fields(&escape_transaction_field("x;\\\r")), vec!["x;\\\r"]);
+    }
+
+    #[test]
+    fn test_timestamp_format() {
+        let now = now_local_timestamp();
+        assert_eq!(now.len(), 19);
+        assert_eq!(&now[10..11], " ");
+    }
+}